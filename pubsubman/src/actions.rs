@@ -2,7 +2,10 @@ use std::time::Duration;
 
 use pubsubman_backend::{
     message::FrontendMessage,
-    model::{PubsubMessageToPublish, SubscriptionName, TopicName},
+    model::{
+        PubsubMessageToPublish, SubscriptionFilter, SubscriptionName, SubscriptionUpdate,
+        TopicName, TopicUpdate,
+    },
 };
 use tokio::{sync::mpsc::Sender, time::sleep};
 use tokio_util::sync::CancellationToken;
@@ -18,13 +21,19 @@ pub fn refresh_topics(front_tx: &Sender<FrontendMessage>, delay_millis: Option<u
     });
 }
 
-pub fn create_subscription(front_tx: &Sender<FrontendMessage>, topic_name: &TopicName) {
+pub fn create_subscription(
+    front_tx: &Sender<FrontendMessage>,
+    topic_name: &TopicName,
+    filter: Option<SubscriptionFilter>,
+) {
     let front_tx = front_tx.to_owned();
     let topic_name = topic_name.to_owned();
 
     tokio::spawn(async move {
         let _ = front_tx
-            .send(FrontendMessage::CreateSubscriptionRequest(topic_name))
+            .send(FrontendMessage::CreateSubscriptionRequest(
+                topic_name, filter,
+            ))
             .await;
     });
 }
@@ -67,14 +76,13 @@ pub fn stream_messages(
     let cancel_token = cancel_token.to_owned();
 
     tokio::spawn(async move {
-        front_tx
+        let _ = front_tx
             .send(FrontendMessage::StreamMessages(
                 topic_name,
                 sub_name,
                 cancel_token,
             ))
-            .await
-            .unwrap();
+            .await;
     });
 }
 
@@ -87,9 +95,141 @@ pub fn publish_message(
     let topic_name = topic_name.to_owned();
 
     tokio::spawn(async move {
-        front_tx
+        let _ = front_tx
             .send(FrontendMessage::PublishMessage(topic_name, message))
-            .await
-            .unwrap();
+            .await;
+    });
+}
+
+pub fn get_topic_metadata(front_tx: &Sender<FrontendMessage>, topic_name: &TopicName) {
+    let front_tx = front_tx.to_owned();
+    let topic_name = topic_name.to_owned();
+
+    tokio::spawn(async move {
+        let _ = front_tx
+            .send(FrontendMessage::GetTopicMetadata(topic_name))
+            .await;
+    });
+}
+
+pub fn get_subscription_metadata(
+    front_tx: &Sender<FrontendMessage>,
+    topic_name: &TopicName,
+    sub_name: &SubscriptionName,
+) {
+    let front_tx = front_tx.to_owned();
+    let topic_name = topic_name.to_owned();
+    let sub_name = sub_name.to_owned();
+
+    tokio::spawn(async move {
+        let _ = front_tx
+            .send(FrontendMessage::GetSubscriptionMetadata(
+                topic_name, sub_name,
+            ))
+            .await;
+    });
+}
+
+pub fn update_topic(
+    front_tx: &Sender<FrontendMessage>,
+    topic_name: &TopicName,
+    update: TopicUpdate,
+) {
+    let front_tx = front_tx.to_owned();
+    let topic_name = topic_name.to_owned();
+
+    tokio::spawn(async move {
+        let _ = front_tx
+            .send(FrontendMessage::UpdateTopic(topic_name, update))
+            .await;
+    });
+}
+
+pub fn update_subscription(
+    front_tx: &Sender<FrontendMessage>,
+    topic_name: &TopicName,
+    sub_name: &SubscriptionName,
+    update: SubscriptionUpdate,
+) {
+    let front_tx = front_tx.to_owned();
+    let topic_name = topic_name.to_owned();
+    let sub_name = sub_name.to_owned();
+
+    tokio::spawn(async move {
+        let _ = front_tx
+            .send(FrontendMessage::UpdateSubscription(
+                topic_name, sub_name, update,
+            ))
+            .await;
+    });
+}
+
+pub fn create_push_subscription(
+    front_tx: &Sender<FrontendMessage>,
+    topic_name: &TopicName,
+    port: u16,
+) {
+    let front_tx = front_tx.to_owned();
+    let topic_name = topic_name.to_owned();
+
+    tokio::spawn(async move {
+        let _ = front_tx
+            .send(FrontendMessage::CreatePushSubscriptionRequest(
+                topic_name, port,
+            ))
+            .await;
+    });
+}
+
+pub fn receive_push_messages(
+    front_tx: &Sender<FrontendMessage>,
+    topic_name: &TopicName,
+    sub_name: &SubscriptionName,
+    port: u16,
+) {
+    let front_tx = front_tx.to_owned();
+    let topic_name = topic_name.to_owned();
+    let sub_name = sub_name.to_owned();
+
+    tokio::spawn(async move {
+        let _ = front_tx
+            .send(FrontendMessage::ReceivePushMessages(
+                topic_name, sub_name, port,
+            ))
+            .await;
+    });
+}
+
+pub fn stop_push_messages(front_tx: &Sender<FrontendMessage>, sub_name: &SubscriptionName) {
+    let front_tx = front_tx.to_owned();
+    let sub_name = sub_name.to_owned();
+
+    tokio::spawn(async move {
+        let _ = front_tx
+            .send(FrontendMessage::StopPushMessages(sub_name))
+            .await;
+    });
+}
+
+pub fn list_created_subscriptions(front_tx: &Sender<FrontendMessage>) {
+    let front_tx = front_tx.to_owned();
+
+    tokio::spawn(async move {
+        let _ = front_tx
+            .send(FrontendMessage::ListCreatedSubscriptions)
+            .await;
+    });
+}
+
+pub fn validate_subscriptions(
+    front_tx: &Sender<FrontendMessage>,
+    subscriptions: Vec<(TopicName, SubscriptionName)>,
+) {
+    let front_tx = front_tx.to_owned();
+
+    tokio::spawn(async move {
+        let _ = front_tx
+            .send(FrontendMessage::ValidateSubscriptions(subscriptions))
+            .await;
     });
 }
@@ -1,114 +1,564 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
 
 use pubsubman_backend::{
     Backend,
-    message::{BackendMessage, FrontendMessage},
-    model::{PubsubMessage, SubscriptionName, TopicName},
+    message::{BackendError, BackendMessage, FrontendMessage},
+    model::{PubsubMessage, SubscriptionFilter, SubscriptionName, TopicName},
 };
 use tokio::sync::mpsc::{Receiver, Sender};
+use tokio_util::sync::CancellationToken;
 
 use crate::{
-    actions::{create_subscription, delete_subscriptions, refresh_topics},
+    actions::{
+        create_push_subscription, create_subscription, delete_subscriptions, receive_push_messages,
+        stop_push_messages, stream_messages, validate_subscriptions,
+    },
     column_settings::ColumnSettings,
-    exit_state::{ExitState, SubscriptionCleanupState},
+    exit_state::ExitState,
+    message_store::MessageStore,
     notifications::Notifications,
+    retention_settings::RetentionSettings,
     settings::Settings,
-    ui::{MessagesView, PublishView, render_selected_message, render_topic_name},
+    spill_store::SpillStore,
+    topic_refresh::TopicRefresh,
+    topic_schema::TopicSchema,
+    ui::{
+        DetailsView, MessagesView, PublishView, SchemaChange, SchemaMenu, SentMessage,
+        StaleSubscriptionsView, TabAction, TopicList, TopicStatus, TraceView, push_sent_message,
+        render_selected_message, render_topic_tabs,
+    },
 };
 
 #[derive(Default, serde::Deserialize, serde::Serialize)]
 struct Memory {
-    pub messages: HashMap<TopicName, Vec<PubsubMessage>>,
+    /// Received messages were previously persisted with the app state, and are moved into the
+    /// [`MessageStore`] when first loaded.
+    #[serde(default, rename = "messages", skip_serializing)]
+    legacy_messages: HashMap<TopicName, Vec<PubsubMessage>>,
     /// The subscriptions this app has created in order to recieve messages.
     subscriptions: HashMap<TopicName, SubscriptionName>,
+    /// The attribute filters that the created subscriptions were created with, if any.
+    #[serde(default)]
+    subscription_filters: HashMap<TopicName, SubscriptionFilter>,
+    /// The push Subscriptions this app has created, that deliver to the local push receiver.
+    #[serde(default)]
+    push_subscriptions: HashMap<TopicName, SubscriptionName>,
     pub column_settings: HashMap<TopicName, ColumnSettings>,
     pub settings: Settings,
+    /// The messages published from this app, most recent last.
+    #[serde(default)]
+    pub sent_messages: HashMap<TopicName, Vec<SentMessage>>,
+    /// The JSON Schemas that message data is validated against.
+    #[serde(default)]
+    pub schemas: HashMap<TopicName, TopicSchema>,
+    /// The limits on the messages kept in the message store.
+    #[serde(default)]
+    pub retention_settings: HashMap<TopicName, RetentionSettings>,
+    /// The Topics pinned to the top of the topics side panel, in the order they were pinned.
+    #[serde(default)]
+    pub favourite_topics: Vec<TopicName>,
 }
 
+/// How often the stored messages are checked against the retention limits.
+const EVICTION_INTERVAL: Duration = Duration::from_secs(5);
+
 pub struct App {
     topic_names: Vec<TopicName>,
-    selected_topic: Option<TopicName>,
     publish_views: HashMap<TopicName, PublishView>,
     messages_views: HashMap<TopicName, MessagesView>,
+    schema_menus: HashMap<TopicName, SchemaMenu>,
+    details_views: HashMap<TopicName, DetailsView>,
     exit_state: ExitState,
     memory: Memory,
     front_tx: Sender<FrontendMessage>,
     back_rx: Receiver<BackendMessage>,
     notifications: Notifications,
-    selected_message: Option<(TopicName, usize)>,
+    selected_message: Option<(TopicName, PubsubMessage)>,
+    message_store: MessageStore,
+    spill_store: Option<SpillStore>,
+    last_eviction: Instant,
+    /// The number of messages received on each Topic since it was last selected.
+    unread_counts: HashMap<TopicName, usize>,
+    trace_view: TraceView,
+    stale_subscriptions_view: StaleSubscriptionsView,
+    topic_list: TopicList,
+    topic_refresh: TopicRefresh,
 }
 
 impl App {
     /// Called once before the first frame.
     pub fn new(cc: &eframe::CreationContext<'_>, emulator_project_id: Option<String>) -> Self {
         let (front_tx, front_rx) = tokio::sync::mpsc::channel(10);
-        let (back_tx, back_rx) = tokio::sync::mpsc::channel(10);
+        // Streams send batches of messages as they arrive, so leave room for the UI to fall
+        // behind by more than a few frames before they have to wait for it.
+        let (back_tx, back_rx) = tokio::sync::mpsc::channel(100);
+
+        let egui_ctx = cc.egui_ctx.clone();
 
         std::thread::spawn(|| {
-            if let Ok(mut backend) = Backend::new(back_tx, front_rx, emulator_project_id) {
+            if let Ok(mut backend) = Backend::new(back_tx, front_rx, emulator_project_id, egui_ctx)
+            {
                 backend.init();
             };
         });
 
-        refresh_topics(&front_tx, None);
+        let mut topic_refresh = TopicRefresh::default();
+        topic_refresh.refresh(&front_tx);
 
-        let memory = cc
+        let mut memory = cc
             .storage
             .and_then(|storage| eframe::get_value::<Memory>(storage, eframe::APP_KEY))
             .unwrap_or_default();
 
+        let storage_dir = eframe::storage_dir("pubsubman");
+        let mut notifications = Notifications::default();
+
+        let mut message_store = storage_dir
+            .as_ref()
+            .map(|dir| {
+                std::fs::create_dir_all(dir)
+                    .map_err(|err| err.to_string())
+                    .and_then(|_| {
+                        MessageStore::open(&dir.join("messages.sqlite3"))
+                            .map_err(|err| err.to_string())
+                    })
+            })
+            .unwrap_or_else(|| Err("No storage directory is available.".to_string()))
+            .unwrap_or_else(|err| {
+                notifications.warning(format!(
+                    "Failed to open the message store, received messages will not be kept after quitting: {}",
+                    err
+                ));
+                MessageStore::open_in_memory().expect("Failed to open in-memory message store")
+            });
+
+        for (topic_name, messages) in memory.legacy_messages.drain() {
+            if let Err(err) = message_store.push_all(&topic_name, &messages) {
+                notifications.warning(format!(
+                    "Failed to move the saved messages for {} into the message store: {}",
+                    topic_name.0, err
+                ));
+            }
+        }
+
+        // Subscriptions from the previous session may since have been deleted or expired.
+        validate_subscriptions(
+            &front_tx,
+            memory
+                .subscriptions
+                .iter()
+                .chain(memory.push_subscriptions.iter())
+                .map(|(topic_name, sub_name)| (topic_name.clone(), sub_name.clone()))
+                .collect(),
+        );
+
+        // Tabs restored from the previous session need subscriptions to show messages.
+        for topic_name in memory.settings.layout.visible_topics() {
+            if !memory.subscriptions.contains_key(topic_name) {
+                create_subscription(&front_tx, topic_name, None);
+            }
+        }
+
         cc.egui_ctx
             .style_mut(|style| style.interaction.selectable_labels = false);
         cc.egui_ctx.set_theme(egui::Theme::Dark);
 
         Self {
             topic_names: vec![],
-            selected_topic: None,
             publish_views: HashMap::default(),
             messages_views: HashMap::default(),
+            schema_menus: HashMap::default(),
+            details_views: HashMap::default(),
             exit_state: ExitState::default(),
             memory,
             front_tx,
             back_rx,
-            notifications: Notifications::default(),
+            notifications,
             selected_message: None,
+            message_store,
+            spill_store: storage_dir.map(|dir| SpillStore::new(dir.join("spilled_messages"))),
+            last_eviction: Instant::now(),
+            unread_counts: HashMap::default(),
+            trace_view: TraceView::default(),
+            stale_subscriptions_view: StaleSubscriptionsView::default(),
+            topic_list: TopicList::default(),
+            topic_refresh,
         }
     }
 
-    fn handle_backend_message(&mut self) {
-        match self.back_rx.try_recv() {
-            Ok(message) => match message {
-                BackendMessage::ClientInitialised(project_id) => self
-                    .notifications
-                    .success(format!("Successfully authenticated to: {}.", project_id)),
-                BackendMessage::TopicsUpdated(topic_names) => {
-                    self.topic_names = topic_names;
+    /// Handles every message that arrived since the last frame, so that receiving messages is
+    /// not throttled to the frame rate. Those that arrive meanwhile are left for the next
+    /// frame, so that a steady stream of them cannot keep the frame from being rendered.
+    fn handle_backend_messages(&mut self, ctx: &egui::Context) {
+        for _ in 0..self.back_rx.len() {
+            match self.back_rx.try_recv() {
+                Ok(message) => self.handle_backend_message(message),
+                Err(_) => break,
+            }
+        }
 
-                    refresh_topics(&self.front_tx, Some(5000));
+        if !self.back_rx.is_empty() {
+            ctx.request_repaint();
+        }
+    }
+
+    fn handle_backend_message(&mut self, message: BackendMessage) {
+        match message {
+            BackendMessage::ClientInitialised(project_id) => self
+                .notifications
+                .success(format!("Successfully authenticated to: {}.", project_id)),
+            BackendMessage::TopicsUpdated(topic_names) => {
+                self.topic_refresh
+                    .on_topics_updated(&self.topic_names, &topic_names);
+                self.topic_names = topic_names;
+
+                // Close the tabs of any deleted Topics.
+                if self.memory.settings.layout.retain_topics(&self.topic_names) {
+                    self.on_layout_changed();
                 }
-                BackendMessage::SubscriptionCreated(topic_name, sub_name) => {
-                    self.memory.subscriptions.insert(topic_name, sub_name);
+            }
+            BackendMessage::SubscriptionCreated(topic_name, sub_name, filter) => {
+                self.on_subscription_created(topic_name, sub_name, filter);
+            }
+            BackendMessage::PushSubscriptionCreated(topic_name, sub_name) => {
+                self.on_push_subscription_created(topic_name, sub_name);
+            }
+            BackendMessage::SubscriptionLost(topic_name, sub_name) => {
+                self.on_subscription_lost(topic_name, sub_name);
+            }
+            BackendMessage::MessagesReceived(topic_name, messages) => {
+                if let Some(messages_view) = self.messages_views.get_mut(&topic_name) {
+                    messages_view.on_messages_received(messages.len());
                 }
-                BackendMessage::MessageReceived(topic_name, message) => {
-                    self.memory
-                        .messages
-                        .entry(topic_name)
-                        .or_default()
-                        .push(message);
+
+                if !self.is_topic_selected(&topic_name) {
+                    *self.unread_counts.entry(topic_name.clone()).or_default() += messages.len();
                 }
-                BackendMessage::SubscriptionsDeleted(results) => {
-                    let successfully_deleted: HashSet<SubscriptionName> =
-                        results.into_iter().filter_map(|s| s.ok()).collect();
 
+                if let Err(err) = self.message_store.push_all(&topic_name, &messages) {
+                    self.notifications.warning(format!(
+                        "Failed to store {} messages received on {}: {}",
+                        messages.len(),
+                        topic_name.0,
+                        err
+                    ));
+                }
+            }
+            BackendMessage::StreamStateChanged(topic_name, sub_name, state) => {
+                // Ignore streams from a Subscription that has since been replaced.
+                if self.memory.subscriptions.get(&topic_name) == Some(&sub_name) {
+                    if let Some(messages_view) = self.messages_views.get_mut(&topic_name) {
+                        messages_view.on_stream_state_changed(state);
+                    }
+                }
+            }
+            BackendMessage::MessagePublished(topic_name, message, message_id) => {
+                push_sent_message(
                     self.memory
-                        .subscriptions
-                        .retain(|_, sub_name| !successfully_deleted.contains(sub_name));
+                        .sent_messages
+                        .entry(topic_name.clone())
+                        .or_default(),
+                    SentMessage::published(&message, message_id.clone()),
+                );
+                self.notifications.published(topic_name, message_id);
+            }
+            BackendMessage::SubscriptionsDeleted(results) => {
+                self.stale_subscriptions_view
+                    .on_subscriptions_deleted(&results);
+                self.exit_state.on_subscriptions_deleted(&results);
+
+                let successfully_deleted: HashSet<SubscriptionName> =
+                    results.into_iter().filter_map(|s| s.ok()).collect();
+
+                self.memory
+                    .subscriptions
+                    .retain(|_, sub_name| !successfully_deleted.contains(sub_name));
+                self.memory
+                    .push_subscriptions
+                    .retain(|_, sub_name| !successfully_deleted.contains(sub_name));
 
-                    self.exit_state.subscription_cleanup_state = SubscriptionCleanupState::Complete;
+                let subscriptions = &self.memory.subscriptions;
+                self.memory
+                    .subscription_filters
+                    .retain(|topic_name, _| subscriptions.contains_key(topic_name));
+            }
+            BackendMessage::CreatedSubscriptionsListed(created) => {
+                let memory = &self.memory;
+                self.stale_subscriptions_view
+                    .on_listed(created, |sub_name| {
+                        memory
+                            .subscriptions
+                            .values()
+                            .chain(memory.push_subscriptions.values())
+                            .any(|in_use| in_use == sub_name)
+                    });
+            }
+            BackendMessage::TopicMetadataFetched(topic_name, metadata) => {
+                self.details_views
+                    .entry(topic_name)
+                    .or_default()
+                    .on_topic_metadata(metadata);
+            }
+            BackendMessage::SubscriptionMetadataFetched(topic_name, metadata) => {
+                self.details_views
+                    .entry(topic_name)
+                    .or_default()
+                    .on_subscription_metadata(metadata);
+            }
+            BackendMessage::TopicMetadataUpdated(topic_name, metadata) => {
+                self.notifications
+                    .success(format!("Updated {}.", topic_name.short_id()));
+                self.details_views
+                    .entry(topic_name)
+                    .or_default()
+                    .on_topic_metadata(metadata);
+            }
+            BackendMessage::SubscriptionMetadataUpdated(topic_name, metadata) => {
+                self.notifications.success(format!(
+                    "Updated the Subscription for {}.",
+                    topic_name.short_id()
+                ));
+                self.details_views
+                    .entry(topic_name)
+                    .or_default()
+                    .on_subscription_metadata(metadata);
+            }
+            BackendMessage::Error(BackendError::GetTopicMetadataFailed(topic_name)) => {
+                if let Some(details_view) = self.details_views.get_mut(&topic_name) {
+                    details_view.on_topic_metadata_failed();
                 }
-                BackendMessage::Error(err) => self.notifications.error(err),
-            },
-            Err(_err) => {}
+
+                self.notifications
+                    .error(BackendError::GetTopicMetadataFailed(topic_name));
+            }
+            BackendMessage::Error(BackendError::GetSubscriptionMetadataFailed(topic_name)) => {
+                if let Some(details_view) = self.details_views.get_mut(&topic_name) {
+                    details_view.on_subscription_metadata_failed();
+                }
+
+                self.notifications
+                    .error(BackendError::GetSubscriptionMetadataFailed(topic_name));
+            }
+            BackendMessage::Error(BackendError::UpdateTopicFailed(topic_name)) => {
+                if let Some(details_view) = self.details_views.get_mut(&topic_name) {
+                    details_view.on_topic_update_failed();
+                }
+
+                self.notifications
+                    .error(BackendError::UpdateTopicFailed(topic_name));
+            }
+            BackendMessage::Error(BackendError::UpdateSubscriptionFailed(topic_name)) => {
+                if let Some(details_view) = self.details_views.get_mut(&topic_name) {
+                    details_view.on_subscription_update_failed();
+                }
+
+                self.notifications
+                    .error(BackendError::UpdateSubscriptionFailed(topic_name));
+            }
+            BackendMessage::Error(BackendError::CreatePushSubscriptionFailed(topic_name)) => {
+                if let Some(messages_view) = self.messages_views.get_mut(&topic_name) {
+                    messages_view.push_messages_enabled = false;
+                }
+
+                self.notifications
+                    .error(BackendError::CreatePushSubscriptionFailed(topic_name));
+            }
+            BackendMessage::Error(BackendError::StreamMessagesFailed(topic_name, sub_name)) => {
+                if let Some(messages_view) = self.messages_views.get_mut(&topic_name) {
+                    messages_view.stop_streaming();
+                }
+
+                self.notifications
+                    .error(BackendError::StreamMessagesFailed(topic_name, sub_name));
+            }
+            BackendMessage::Error(BackendError::PushMessagesFailed(topic_name, sub_name)) => {
+                if let Some(messages_view) = self.messages_views.get_mut(&topic_name) {
+                    messages_view.push_messages_enabled = false;
+                }
+
+                self.notifications
+                    .error(BackendError::PushMessagesFailed(topic_name, sub_name));
+            }
+            BackendMessage::Error(BackendError::PushReceiverFailed(port)) => {
+                for messages_view in self.messages_views.values_mut() {
+                    messages_view.push_messages_enabled = false;
+                }
+
+                self.notifications
+                    .error(BackendError::PushReceiverFailed(port));
+            }
+            BackendMessage::Error(BackendError::ListCreatedSubscriptionsFailed) => {
+                self.stale_subscriptions_view.on_list_failed();
+                self.notifications
+                    .error(BackendError::ListCreatedSubscriptionsFailed);
+            }
+            BackendMessage::Error(BackendError::GetTopicsFailed) => {
+                // Refreshing is retried with backoff, so only notify of the first failure.
+                if self.topic_refresh.on_refresh_failed() {
+                    self.notifications.error(BackendError::GetTopicsFailed);
+                }
+            }
+            BackendMessage::Error(err) => self.notifications.error(err),
+        }
+    }
+
+    fn on_subscription_created(
+        &mut self,
+        topic_name: TopicName,
+        sub_name: SubscriptionName,
+        filter: Option<SubscriptionFilter>,
+    ) {
+        match filter {
+            Some(filter) => {
+                self.memory
+                    .subscription_filters
+                    .insert(topic_name.clone(), filter);
+            }
+            None => {
+                self.memory.subscription_filters.remove(&topic_name);
+            }
+        }
+
+        let previous_sub_name = self
+            .memory
+            .subscriptions
+            .insert(topic_name.clone(), sub_name.clone());
+
+        if previous_sub_name.as_ref() == Some(&sub_name) {
+            return;
+        }
+
+        // Move any active stream over to the replacement subscription, or resume one whose
+        // subscription was lost.
+        if let Some(cancel_token) = self
+            .messages_views
+            .get_mut(&topic_name)
+            .and_then(|messages_view| messages_view.stream_messages_cancel_token.as_mut())
+        {
+            cancel_token.cancel();
+            *cancel_token = CancellationToken::new();
+            stream_messages(&self.front_tx, &topic_name, &sub_name, cancel_token);
+        }
+
+        if let Some(previous_sub_name) = previous_sub_name {
+            delete_subscriptions(&self.front_tx, vec![previous_sub_name]);
+        }
+    }
+
+    /// Replaces a Subscription that was deleted or expired with a new one for the Topic.
+    fn on_subscription_lost(&mut self, topic_name: TopicName, sub_name: SubscriptionName) {
+        if self.memory.subscriptions.get(&topic_name) == Some(&sub_name) {
+            self.memory.subscriptions.remove(&topic_name);
+
+            let filter = self.memory.subscription_filters.get(&topic_name).cloned();
+            create_subscription(&self.front_tx, &topic_name, filter);
+        } else if self.memory.push_subscriptions.get(&topic_name) == Some(&sub_name) {
+            self.memory.push_subscriptions.remove(&topic_name);
+
+            if self
+                .messages_views
+                .get(&topic_name)
+                .is_some_and(|messages_view| messages_view.push_messages_enabled)
+            {
+                create_push_subscription(
+                    &self.front_tx,
+                    &topic_name,
+                    self.memory.settings.push_receiver.port,
+                );
+            }
+        } else {
+            // The Subscription was already replaced.
+            return;
+        }
+
+        self.notifications.warning(format!(
+            "The Subscription for {} was deleted or has expired, so a new one will be created. Messages published before now that had not been received may have been lost.",
+            topic_name.0
+        ));
+    }
+
+    fn on_push_subscription_created(&mut self, topic_name: TopicName, sub_name: SubscriptionName) {
+        // Receiving may have been disabled while the Subscription was being created.
+        if !self
+            .messages_views
+            .get(&topic_name)
+            .is_some_and(|messages_view| messages_view.push_messages_enabled)
+        {
+            stop_push_messages(&self.front_tx, &sub_name);
+        }
+
+        self.memory.push_subscriptions.insert(topic_name, sub_name);
+    }
+
+    fn evict_messages(&mut self, topic_name: &TopicName) {
+        let retention = self
+            .memory
+            .retention_settings
+            .get(topic_name)
+            .cloned()
+            .unwrap_or_default();
+
+        let evicted = match self
+            .message_store
+            .evict(topic_name, &retention, chrono::Utc::now())
+        {
+            Ok(evicted) => evicted,
+            Err(err) => {
+                self.notifications.warning(format!(
+                    "Failed to evict stored messages for {}: {}",
+                    topic_name.0, err
+                ));
+                return;
+            }
+        };
+
+        if evicted.is_empty() {
+            return;
+        }
+
+        if let Some(spill_store) = self.spill_store.as_mut() {
+            if let Err(err) = spill_store.append(topic_name, &evicted) {
+                self.notifications.warning(format!(
+                    "Failed to spill evicted messages to disk for {}: {}",
+                    topic_name.0, err
+                ));
+            }
+        }
+    }
+
+    /// Messages are evicted periodically, rather than as each is received, to keep the cost of
+    /// checking the limits off the receive path.
+    fn evict_stored_messages(&mut self, ctx: &egui::Context) {
+        // Messages expire by age without anything else happening, so keep checking for them.
+        if self
+            .memory
+            .retention_settings
+            .values()
+            .any(|retention| retention.max_age_hours.is_some())
+        {
+            ctx.request_repaint_after(EVICTION_INTERVAL);
+        }
+
+        if self.last_eviction.elapsed() < EVICTION_INTERVAL {
+            return;
+        }
+
+        self.last_eviction = Instant::now();
+
+        let topic_names = match self.message_store.topic_names() {
+            Ok(topic_names) => topic_names,
+            Err(err) => {
+                self.notifications
+                    .warning(format!("Failed to read the stored topics: {}", err));
+                return;
+            }
+        };
+
+        for topic_name in topic_names {
+            self.evict_messages(&topic_name);
         }
     }
 
@@ -116,6 +566,19 @@ impl App {
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
                 ui.menu_button("File", |ui| {
+                    if ui
+                        .button("Clean Up Stale pubsubman Subscriptions…")
+                        .on_hover_text(
+                            "Find and delete Subscriptions created by pubsubman that are no longer in use.",
+                        )
+                        .clicked()
+                    {
+                        self.stale_subscriptions_view.open(&self.front_tx);
+                        ui.close();
+                    }
+
+                    ui.separator();
+
                     if ui.button("Quit").clicked() {
                         self.exit_state.show_exit_dialogue = true;
                     }
@@ -128,6 +591,17 @@ impl App {
                             " Publish Message Panel",
                         );
                     });
+
+                    ui.horizontal(|ui| {
+                        ui.checkbox(
+                            &mut self.memory.settings.view.group_topics_by_prefix,
+                            " Group Topics by Prefix",
+                        );
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.trace_view.is_open, " Trace Messages");
+                    });
                 });
             });
         });
@@ -139,164 +613,453 @@ impl App {
             .default_width(250.0)
             .show(ctx, |ui| {
                 egui::ScrollArea::vertical().show(ui, |ui| {
-                    ui.heading("Topics");
+                    ui.horizontal(|ui| {
+                        ui.heading("Topics");
+
+                        self.topic_refresh.show(
+                            ui,
+                            &self.front_tx,
+                            &mut self.memory.settings.topic_refresh,
+                        );
 
-                    let topic_names = self.topic_names.clone();
-                    for topic_name in topic_names {
-                        let is_selected = self.is_topic_selected(&topic_name);
+                        let streaming_count = self
+                            .messages_views
+                            .values()
+                            .filter(|messages_view| messages_view.is_receiving())
+                            .count();
 
-                        render_topic_name(ui, &topic_name, is_selected, || {
-                            self.on_topic_click(&topic_name)
-                        });
+                        if streaming_count > 0 {
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    if ui
+                                        .button("Stop All")
+                                        .on_hover_text(format!(
+                                            "Stop receiving messages on {} Topic{}.",
+                                            streaming_count,
+                                            if streaming_count == 1 { "" } else { "s" }
+                                        ))
+                                        .clicked()
+                                    {
+                                        self.stop_all_streams();
+                                    }
+                                },
+                            );
+                        }
+                    });
+
+                    ui.add_space(4.0);
+
+                    // Recently removed Topics are still listed for a while, to highlight them.
+                    let topic_names: Vec<TopicName> = self
+                        .topic_names
+                        .iter()
+                        .chain(self.topic_refresh.removed_topics())
+                        .cloned()
+                        .collect();
+
+                    let clicked_topic = self.topic_list.show(
+                        ui,
+                        &topic_names,
+                        &mut self.memory.favourite_topics,
+                        self.memory.settings.view.group_topics_by_prefix,
+                        |topic_name| TopicStatus {
+                            is_added: self.topic_refresh.is_added(topic_name),
+                            is_removed: self.topic_refresh.is_removed(topic_name),
+                            is_selected: self.memory.settings.layout.is_topic_visible(topic_name),
+                            is_streaming: self
+                                .messages_views
+                                .get(topic_name)
+                                .is_some_and(|messages_view| messages_view.is_receiving()),
+                            unread_count: self
+                                .unread_counts
+                                .get(topic_name)
+                                .copied()
+                                .unwrap_or_default(),
+                            ..Default::default()
+                        },
+                    );
+
+                    if let Some(topic_name) = clicked_topic {
+                        self.on_topic_click(&topic_name);
                     }
                 });
             });
     }
 
     fn on_topic_click(&mut self, topic_name: &TopicName) {
-        if self.is_topic_selected(topic_name) {
-            return;
+        // Streams keep running in the background, so that several Topics can be watched at once.
+        self.memory.settings.layout.open(topic_name);
+        self.on_layout_changed();
+    }
+
+    /// Ensures the visible Topics have subscriptions, and closes the selected message if its
+    /// Topic is no longer visible.
+    fn on_layout_changed(&mut self) {
+        let visible_topics: Vec<TopicName> = self
+            .memory
+            .settings
+            .layout
+            .visible_topics()
+            .cloned()
+            .collect();
+
+        for topic_name in &visible_topics {
+            if !self.memory.subscriptions.contains_key(topic_name) {
+                create_subscription(&self.front_tx, topic_name, None);
+            }
         }
 
-        if let Some(cancel_token) = self
-            .selected_topic
-            .take()
-            .and_then(|selected_topic| self.messages_views.get_mut(&selected_topic))
-            .and_then(|messages_view| {
-                messages_view.stream_messages_enabled = false;
-                messages_view.stream_messages_cancel_token.take()
-            })
+        if self
+            .selected_message
+            .as_ref()
+            .is_some_and(|(topic_name, _)| !visible_topics.contains(topic_name))
         {
-            cancel_token.cancel();
+            self.selected_message.take();
         }
+    }
 
-        self.selected_message.take();
+    fn stop_all_streams(&mut self) {
+        for (topic_name, messages_view) in self.messages_views.iter_mut() {
+            messages_view.stop_streaming();
 
-        self.selected_topic = Some(topic_name.clone());
+            if messages_view.push_messages_enabled {
+                messages_view.push_messages_enabled = false;
 
-        if !self.memory.subscriptions.contains_key(topic_name) {
-            create_subscription(&self.front_tx, topic_name);
+                if let Some(sub_name) = self.memory.push_subscriptions.get(topic_name) {
+                    stop_push_messages(&self.front_tx, sub_name);
+                }
+            }
         }
     }
 
     fn is_topic_selected(&self, topic_name: &TopicName) -> bool {
-        self.selected_topic
-            .as_ref()
-            .is_some_and(|selected_topic| selected_topic == topic_name)
+        self.memory.settings.layout.is_topic_visible(topic_name)
     }
 
     fn render_central_panel(&mut self, ctx: &egui::Context) {
-        match &self.selected_topic {
-            Some(selected_topic) => {
-                let selected_message =
-                    self.selected_message
-                        .as_ref()
-                        .and_then(|(topic_name, idx)| {
-                            self.memory
-                                .messages
-                                .get(topic_name)
-                                .and_then(|messages| messages.get(*idx))
-                        });
+        egui::CentralPanel::default()
+            .frame(
+                egui::Frame::central_panel(&ctx.style())
+                    .inner_margin(0.0)
+                    .outer_margin(0.0),
+            )
+            .show(ctx, |ui| {
+                let layout = &self.memory.settings.layout;
+
+                if layout.panes.iter().all(|pane| pane.tabs.is_empty()) {
+                    ui.with_layout(
+                        egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
+                        |ui| {
+                            ui.heading("Select a Topic.");
+                        },
+                    );
+                    return;
+                }
+
+                let pane_count = layout.panes.len();
+                let pane_width = ui.available_width() / pane_count as f32;
+                let mut tab_action = None;
 
-                egui::TopBottomPanel::top("topic_view_top_panel")
-                    .frame(egui::Frame::side_top_panel(&ctx.style()).inner_margin(8.0))
-                    .show(ctx, |ui| {
-                        ui.vertical_centered(|ui| {
-                            ui.heading(&selected_topic.0);
+                // Every pane but the last is docked to the left of the ones after it.
+                for pane_idx in 0..pane_count - 1 {
+                    egui::SidePanel::left(egui::Id::new(("topic_pane", pane_idx)))
+                        .frame(egui::Frame::NONE)
+                        .resizable(true)
+                        .default_width(pane_width)
+                        .show_inside(ui, |ui| {
+                            if let Some(action) = self.render_pane(ui, pane_idx) {
+                                tab_action = Some((pane_idx, action));
+                            }
                         });
-                    });
+                }
 
-                egui::SidePanel::right("selected_message")
+                egui::CentralPanel::default()
                     .frame(egui::Frame::NONE)
-                    .resizable(true)
-                    .show_animated(ctx, selected_message.is_some(), |ui| {
-                        if let Some(message) = selected_message {
-                            render_selected_message(
-                                ctx,
-                                ui,
-                                &self.front_tx,
-                                message,
-                                selected_topic,
-                                || {
-                                    self.selected_message.take();
-                                },
-                            );
+                    .show_inside(ui, |ui| {
+                        if let Some(action) = self.render_pane(ui, pane_count - 1) {
+                            tab_action = Some((pane_count - 1, action));
                         }
                     });
 
-                let bottom_panel_height = ctx.available_rect().height() / 2.0;
+                if let Some((pane_idx, action)) = tab_action {
+                    let layout = &mut self.memory.settings.layout;
 
-                egui::TopBottomPanel::bottom("topic_view_bottom_panel")
-                    .frame(egui::Frame::side_top_panel(&ctx.style()).inner_margin(8.0))
-                    .show_animated(
-                        ctx,
-                        self.memory.settings.view.show_publish_message_panel,
-                        |ui| {
-                            egui::ScrollArea::vertical()
-                                .auto_shrink([false, true])
-                                .min_scrolled_height(bottom_panel_height)
-                                .show(ui, |ui| {
-                                    self.publish_views
-                                        .entry(selected_topic.clone())
-                                        .or_default()
-                                        .show(ui, &self.front_tx, selected_topic);
-                                });
-                        },
-                    );
+                    match action {
+                        TabAction::Activate(tab_idx) => {
+                            layout.panes[pane_idx].active_tab = tab_idx;
+                            layout.focused_pane = pane_idx;
+                        }
+                        TabAction::Close(tab_idx) => {
+                            layout.close(pane_idx, tab_idx);
+                        }
+                        TabAction::SplitRight(tab_idx) => layout.split_right(pane_idx, tab_idx),
+                        TabAction::MergeLeft => layout.merge_left(pane_idx),
+                    }
 
-                egui::CentralPanel::default()
-                    .frame(
-                        egui::Frame::central_panel(&ctx.style())
-                            .inner_margin(0.0)
-                            .outer_margin(0.0),
-                    )
-                    .show(ctx, |ui| {
-                        match self.memory.subscriptions.get(selected_topic) {
-                            Some(sub_name) => {
-                                let messages_view = self
-                                    .messages_views
-                                    .entry(selected_topic.clone())
-                                    .or_default();
-
-                                messages_view.show(
-                                    ui,
+                    self.on_layout_changed();
+                }
+            });
+    }
+
+    fn render_pane(&mut self, ui: &mut egui::Ui, pane_idx: usize) -> Option<TabAction> {
+        let layout = &mut self.memory.settings.layout;
+
+        // Topics selected from the side panel open in the pane that was last interacted with.
+        if ui.ui_contains_pointer() && ui.input(|i| i.pointer.any_pressed()) {
+            layout.focused_pane = pane_idx;
+        }
+
+        let pane = layout.panes[pane_idx].clone();
+        let is_focused = layout.panes.len() > 1 && layout.focused_pane == pane_idx;
+
+        let tab_action = egui::TopBottomPanel::top(egui::Id::new(("topic_tabs", pane_idx)))
+            .frame(egui::Frame::side_top_panel(ui.style()).inner_margin(egui::vec2(8.0, 4.0)))
+            .show_inside(ui, |ui| {
+                render_topic_tabs(ui, &pane, is_focused, pane_idx > 0)
+            })
+            .inner;
+
+        match pane.active_topic() {
+            Some(topic_name) => self.render_topic_view(ui, topic_name),
+            None => {
+                ui.with_layout(
+                    egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
+                    |ui| {
+                        ui.heading("Select a Topic.");
+                    },
+                );
+            }
+        }
+
+        tab_action
+    }
+
+    fn render_topic_view(&mut self, ui: &mut egui::Ui, selected_topic: &TopicName) {
+        self.unread_counts.remove(selected_topic);
+        self.topic_refresh.mark_seen(selected_topic);
+
+        let ctx = ui.ctx().clone();
+
+        egui::TopBottomPanel::top(egui::Id::new(("topic_view_top_panel", &selected_topic.0)))
+            .frame(egui::Frame::side_top_panel(ui.style()).inner_margin(8.0))
+            .show_inside(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading(selected_topic.short_id())
+                        .on_hover_text(&selected_topic.0);
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let details_view = self
+                            .details_views
+                            .entry(selected_topic.clone())
+                            .or_default();
+
+                        if ui
+                            .selectable_label(details_view.is_open, "Details")
+                            .on_hover_text("Show the configuration of the Topic and Subscription.")
+                            .clicked()
+                        {
+                            details_view.is_open = !details_view.is_open;
+
+                            if details_view.is_open {
+                                details_view.fetch(
                                     &self.front_tx,
                                     selected_topic,
-                                    sub_name,
-                                    self.memory
-                                        .column_settings
-                                        .entry(selected_topic.clone())
-                                        .or_default(),
-                                    self.memory.messages.get(selected_topic).unwrap_or(&vec![]),
-                                    |idx| {
-                                        self.selected_message = Some((selected_topic.clone(), idx))
-                                    },
+                                    self.memory.subscriptions.get(selected_topic),
                                 );
                             }
-                            None => {
-                                ui.with_layout(
-                                    egui::Layout::centered_and_justified(
-                                        egui::Direction::LeftToRight,
-                                    ),
-                                    |ui| {
-                                        ui.spinner();
-                                    },
-                                );
+                        }
+
+                        let schema_change = self
+                            .schema_menus
+                            .entry(selected_topic.clone())
+                            .or_default()
+                            .show(ui, self.memory.schemas.get(selected_topic));
+
+                        match schema_change {
+                            Some(SchemaChange::Attached(schema)) => {
+                                self.memory.schemas.insert(selected_topic.clone(), *schema);
                             }
+                            Some(SchemaChange::Detached) => {
+                                self.memory.schemas.remove(selected_topic);
+                            }
+                            None => {}
+                        }
+
+                        let retention_changed = self
+                            .memory
+                            .retention_settings
+                            .entry(selected_topic.clone())
+                            .or_default()
+                            .show(ui);
+
+                        if retention_changed {
+                            self.evict_messages(selected_topic);
                         }
                     });
-            }
-            None => {
-                egui::CentralPanel::default().show(ctx, |ui| {
-                    ui.with_layout(
-                        egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
-                        |ui| {
-                            ui.heading("Select a Topic.");
-                        },
-                    );
                 });
+            });
+
+        if let Some(details_view) = self
+            .details_views
+            .get_mut(selected_topic)
+            .filter(|details_view| details_view.is_open)
+        {
+            details_view.show(
+                &ctx,
+                &self.front_tx,
+                selected_topic,
+                self.memory.subscriptions.get(selected_topic),
+            );
+        }
+
+        let schema = self.memory.schemas.get(selected_topic);
+        let selected_message = self
+            .selected_message
+            .as_ref()
+            .filter(|(topic_name, _)| topic_name == selected_topic)
+            .map(|(_, message)| message);
+        let mut close_selected_message = false;
+
+        egui::SidePanel::right(egui::Id::new(("selected_message", &selected_topic.0)))
+            .frame(egui::Frame::NONE)
+            .resizable(true)
+            .show_animated_inside(ui, selected_message.is_some(), |ui| {
+                if let Some(message) = selected_message {
+                    render_selected_message(
+                        &ctx,
+                        ui,
+                        &self.front_tx,
+                        message,
+                        selected_topic,
+                        schema,
+                        self.messages_views
+                            .get(selected_topic)
+                            .and_then(MessagesView::data_search_regex),
+                        self.memory
+                            .column_settings
+                            .entry(selected_topic.clone())
+                            .or_default(),
+                        || close_selected_message = true,
+                        |(column, value)| self.trace_view.trace(column, value),
+                    );
+                }
+            });
+
+        if close_selected_message {
+            self.selected_message.take();
+        }
+
+        let bottom_panel_height = ui.available_height() / 2.0;
+
+        egui::TopBottomPanel::bottom(egui::Id::new((
+            "topic_view_bottom_panel",
+            &selected_topic.0,
+        )))
+        .frame(egui::Frame::side_top_panel(ui.style()).inner_margin(8.0))
+        .show_animated_inside(
+            ui,
+            self.memory.settings.view.show_publish_message_panel,
+            |ui| {
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, true])
+                    .min_scrolled_height(bottom_panel_height)
+                    .show(ui, |ui| {
+                        self.publish_views
+                            .entry(selected_topic.clone())
+                            .or_default()
+                            .show(
+                                ui,
+                                &self.front_tx,
+                                selected_topic,
+                                self.memory
+                                    .sent_messages
+                                    .get(selected_topic)
+                                    .map_or(&[], Vec::as_slice),
+                                schema,
+                            );
+                    });
+            },
+        );
+
+        let push_port = self.memory.settings.push_receiver.port;
+
+        egui::CentralPanel::default()
+            .frame(
+                egui::Frame::central_panel(ui.style())
+                    .inner_margin(0.0)
+                    .outer_margin(0.0),
+            )
+            .show_inside(ui, |ui| {
+                match self.memory.subscriptions.get(selected_topic) {
+                    Some(sub_name) => {
+                        let messages_view = self
+                            .messages_views
+                            .entry(selected_topic.clone())
+                            .or_default();
+
+                        messages_view.show(
+                            ui,
+                            &self.front_tx,
+                            selected_topic,
+                            sub_name,
+                            self.memory.subscription_filters.get(selected_topic),
+                            self.memory.push_subscriptions.get(selected_topic),
+                            &mut self.memory.settings.push_receiver,
+                            self.memory
+                                .column_settings
+                                .entry(selected_topic.clone())
+                                .or_default(),
+                            &self.message_store,
+                            schema,
+                            self.spill_store.as_mut(),
+                            |message| {
+                                self.selected_message =
+                                    Some((selected_topic.clone(), message.clone()))
+                            },
+                        );
+                    }
+                    None => {
+                        ui.with_layout(
+                            egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
+                            |ui| {
+                                ui.spinner();
+                            },
+                        );
+                    }
+                }
+            });
+
+        if self.memory.settings.push_receiver.port != push_port {
+            self.on_push_port_changed();
+        }
+    }
+
+    /// Moves the push Subscriptions being received over to the receiver on the new port.
+    fn on_push_port_changed(&mut self) {
+        let port = self.memory.settings.push_receiver.port;
+
+        for (topic_name, messages_view) in &self.messages_views {
+            if !messages_view.push_messages_enabled {
+                continue;
             }
-        };
+
+            if let Some(sub_name) = self.memory.push_subscriptions.get(topic_name) {
+                receive_push_messages(&self.front_tx, topic_name, sub_name, port);
+            }
+        }
+    }
+
+    fn render_trace_view(&mut self, ctx: &egui::Context) {
+        if !self.trace_view.is_open {
+            return;
+        }
+
+        if let Some((topic_name, message)) = self.trace_view.show(ctx, &self.message_store) {
+            self.memory.settings.layout.open(&topic_name);
+            self.selected_message = Some((topic_name, message));
+            self.on_layout_changed();
+        }
     }
 
     fn handle_exit(&mut self, ctx: &egui::Context) {
@@ -305,7 +1068,13 @@ impl App {
             ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
         }
 
-        let sub_names = self.memory.subscriptions.values().cloned().collect();
+        let sub_names = self
+            .memory
+            .subscriptions
+            .values()
+            .chain(self.memory.push_subscriptions.values())
+            .cloned()
+            .collect();
         let cleanup_subscriptions = |sub_names: Vec<SubscriptionName>| {
             delete_subscriptions(&self.front_tx, sub_names);
         };
@@ -327,11 +1096,19 @@ impl App {
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        ctx.request_repaint();
-        self.handle_backend_message();
+        self.handle_backend_messages(ctx);
+        self.topic_refresh
+            .refresh_if_due(ctx, &self.front_tx, &self.memory.settings.topic_refresh);
+        self.evict_stored_messages(ctx);
         self.render_top_panel(ctx);
         self.render_topics_panel(ctx);
         self.render_central_panel(ctx);
+        self.render_trace_view(ctx);
+
+        if self.stale_subscriptions_view.is_open {
+            self.stale_subscriptions_view.show(ctx, &self.front_tx);
+        }
+
         self.handle_exit(ctx);
         self.notifications.show(ctx);
     }
@@ -1,23 +1,512 @@
-#[derive(Clone, serde::Deserialize, serde::Serialize)]
+use std::{cmp::Ordering, fmt::Display};
+
+use chrono::{DateTime, Local, Utc};
+use pubsubman_backend::model::PubsubMessage;
+use serde_json::Value;
+
+use crate::message_store::MessageQuery;
+
+/// The columns of the messages table for a Topic, in the order they are shown.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(from = "PersistedColumnSettings")]
 pub struct ColumnSettings {
-    pub show_publish_time: bool,
+    pub columns: Vec<ColumnConfig>,
+    pub sort: Option<ColumnSort>,
+    /// The input for a new attribute or JSON pointer column.
+    #[serde(skip)]
+    new_column: (NewColumnKind, String),
 }
 
 impl Default for ColumnSettings {
     fn default() -> Self {
+        let column = |kind, visible| ColumnConfig {
+            kind,
+            visible,
+            width: None,
+            filter: String::new(),
+        };
+
         Self {
-            show_publish_time: true,
+            columns: vec![
+                column(ColumnKind::Id, true),
+                column(ColumnKind::PublishTime, true),
+                column(ColumnKind::ReceiveTime, false),
+                column(ColumnKind::OrderingKey, false),
+                column(ColumnKind::DeliveryAttempt, false),
+                column(ColumnKind::PayloadSize, false),
+                column(ColumnKind::Data, true),
+            ],
+            sort: None,
+            new_column: Default::default(),
         }
     }
 }
 
+/// Column settings were previously only a toggle for the Publish Time column.
+#[derive(serde::Deserialize)]
+struct PersistedColumnSettings {
+    columns: Option<Vec<ColumnConfig>>,
+    sort: Option<ColumnSort>,
+    show_publish_time: Option<bool>,
+}
+
+impl From<PersistedColumnSettings> for ColumnSettings {
+    fn from(value: PersistedColumnSettings) -> Self {
+        let mut settings = Self::default();
+
+        if let Some(columns) = value.columns {
+            settings.columns = columns;
+        }
+
+        if let Some(show_publish_time) = value.show_publish_time {
+            settings.set_visible(&ColumnKind::PublishTime, show_publish_time);
+        }
+
+        settings.sort = value.sort;
+        settings
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct ColumnConfig {
+    pub kind: ColumnKind,
+    pub visible: bool,
+    /// The width the column was last resized to.
+    pub width: Option<f32>,
+    /// Text that the column value must contain, for custom columns.
+    #[serde(default)]
+    pub filter: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ColumnKind {
+    Id,
+    PublishTime,
+    ReceiveTime,
+    OrderingKey,
+    DeliveryAttempt,
+    PayloadSize,
+    Attribute(String),
+    JsonPointer(String),
+    Data,
+}
+
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ColumnSort {
+    pub kind: ColumnKind,
+    pub descending: bool,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+enum NewColumnKind {
+    #[default]
+    Attribute,
+    JsonPointer,
+}
+
 impl ColumnSettings {
+    pub fn visible_columns(&self) -> impl Iterator<Item = &ColumnConfig> {
+        self.columns.iter().filter(|column| column.visible)
+    }
+
+    fn set_visible(&mut self, kind: &ColumnKind, visible: bool) {
+        if let Some(column) = self.columns.iter_mut().find(|column| column.kind == *kind) {
+            column.visible = visible;
+        }
+    }
+
+    /// Sorts by the column ascending, then descending, then not at all.
+    pub fn toggle_sort(&mut self, kind: &ColumnKind) {
+        self.sort = match self.sort.take() {
+            Some(sort) if sort.kind == *kind && !sort.descending => Some(ColumnSort {
+                kind: kind.clone(),
+                descending: true,
+            }),
+            Some(sort) if sort.kind == *kind => None,
+            _ => Some(ColumnSort {
+                kind: kind.clone(),
+                descending: false,
+            }),
+        };
+    }
+
+    /// Adds the column, or shows it if it has already been added.
+    pub fn add_column(&mut self, kind: ColumnKind) {
+        if self.columns.iter().any(|column| column.kind == kind) {
+            self.set_visible(&kind, true);
+            return;
+        }
+
+        // Keep the Data column, which fills the remaining width, last by default.
+        let idx = self
+            .columns
+            .iter()
+            .position(|column| column.kind == ColumnKind::Data)
+            .unwrap_or(self.columns.len());
+
+        self.columns.insert(
+            idx,
+            ColumnConfig {
+                kind,
+                visible: true,
+                width: None,
+                filter: String::new(),
+            },
+        );
+    }
+
+    pub fn remove_column(&mut self, kind: &ColumnKind) {
+        self.columns.retain(|column| column.kind != *kind);
+
+        if self.sort.as_ref().is_some_and(|sort| sort.kind == *kind) {
+            self.sort = None;
+        }
+    }
+
+    /// The filters on visible custom columns.
+    fn filters(&self) -> impl Iterator<Item = (&ColumnKind, &str)> {
+        self.visible_columns()
+            .filter(|column| column.kind.is_custom() && !column.filter.is_empty())
+            .map(|column| (&column.kind, column.filter.as_str()))
+    }
+
+    pub fn has_filters(&self) -> bool {
+        self.filters().next().is_some()
+    }
+
+    pub fn matches(&self, message: &PubsubMessage) -> bool {
+        self.filters().all(|(kind, filter)| {
+            kind.text(message)
+                .to_ascii_lowercase()
+                .contains(&filter.to_ascii_lowercase())
+        })
+    }
+
+    pub fn sort(&self, messages: &mut [&PubsubMessage]) {
+        if let Some(sort) = &self.sort {
+            messages.sort_by(|a, b| {
+                let ordering = sort.kind.compare(a, b);
+                if sort.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+    }
+
+    /// Adds the column filters and sort to a query of stored messages, matching as
+    /// [`ColumnSettings::matches`] and [`ColumnSettings::sort`] do.
+    pub fn apply_to(&self, query: &mut MessageQuery) {
+        for (kind, filter) in self.filters() {
+            query.column_contains(kind, filter);
+        }
+
+        if let Some(sort) = &self.sort {
+            query.sort_by(&sort.kind, sort.descending);
+        }
+    }
+
     pub fn show(&mut self, ui: &mut egui::Ui) {
         ui.visuals_mut().widgets.inactive.weak_bg_fill = egui::Color32::from_gray(32);
         ui.menu_button("Columns ⏷", |ui| {
+            let num_columns = self.columns.len();
+            let mut move_column = None;
+            let mut remove_column = None;
+
+            egui::Grid::new("column_settings_grid")
+                .num_columns(2)
+                .spacing((8.0, 4.0))
+                .show(ui, |ui| {
+                    for (idx, column) in self.columns.iter_mut().enumerate() {
+                        // The ID column is always shown, as it is how messages are selected.
+                        ui.add_enabled(
+                            column.kind != ColumnKind::Id,
+                            egui::Checkbox::new(&mut column.visible, format!(" {}", column.kind)),
+                        );
+
+                        ui.horizontal(|ui| {
+                            if ui.add_enabled(idx > 0, egui::Button::new("⏶")).clicked() {
+                                move_column = Some((idx, idx - 1));
+                            }
+
+                            if ui
+                                .add_enabled(idx + 1 < num_columns, egui::Button::new("⏷"))
+                                .clicked()
+                            {
+                                move_column = Some((idx, idx + 1));
+                            }
+
+                            if column.kind.is_custom() && ui.button("🗑").clicked() {
+                                remove_column = Some(idx);
+                            }
+                        });
+
+                        ui.end_row();
+                    }
+                });
+
+            if let Some((from, to)) = move_column {
+                self.columns.swap(from, to);
+            }
+
+            if let Some(idx) = remove_column {
+                let kind = self.columns[idx].kind.clone();
+                self.remove_column(&kind);
+            }
+
+            ui.separator();
+
+            let mut new_column = None;
+
             ui.horizontal(|ui| {
-                ui.checkbox(&mut self.show_publish_time, " Publish Time");
+                let (new_column_kind, text) = &mut self.new_column;
+
+                egui::ComboBox::from_id_salt("new_column_kind_combo_box")
+                    .selected_text(match new_column_kind {
+                        NewColumnKind::Attribute => "Attribute",
+                        NewColumnKind::JsonPointer => "JSON Pointer",
+                    })
+                    .width(90.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(new_column_kind, NewColumnKind::Attribute, "Attribute");
+                        ui.selectable_value(
+                            new_column_kind,
+                            NewColumnKind::JsonPointer,
+                            "JSON Pointer",
+                        );
+                    });
+
+                let hint_text = match new_column_kind {
+                    NewColumnKind::Attribute => "Key",
+                    NewColumnKind::JsonPointer => "/order/id",
+                };
+
+                ui.add(
+                    egui::TextEdit::singleline(text)
+                        .desired_width(120.0)
+                        .code_editor()
+                        .hint_text(hint_text),
+                );
+
+                let is_valid = match new_column_kind {
+                    NewColumnKind::Attribute => !text.is_empty(),
+                    NewColumnKind::JsonPointer => text.starts_with('/'),
+                };
+
+                if ui
+                    .add_enabled(is_valid, egui::Button::new("Add"))
+                    .on_disabled_hover_text(match new_column_kind {
+                        NewColumnKind::Attribute => "Enter an attribute key.",
+                        NewColumnKind::JsonPointer => "JSON pointers start with '/'.",
+                    })
+                    .clicked()
+                {
+                    new_column = Some(match new_column_kind {
+                        NewColumnKind::Attribute => ColumnKind::Attribute(text.clone()),
+                        NewColumnKind::JsonPointer => ColumnKind::JsonPointer(text.clone()),
+                    });
+                    text.clear();
+                }
             });
+
+            if let Some(kind) = new_column {
+                self.add_column(kind);
+            }
         });
     }
 }
+
+impl ColumnKind {
+    /// Whether the column was added by the user, rather than being one of the defaults.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, ColumnKind::Attribute(_) | ColumnKind::JsonPointer(_))
+    }
+
+    /// The value of the column for the message, as shown in the table. Not used for the ID and
+    /// Data columns, which are rendered specially.
+    pub fn text(&self, message: &PubsubMessage) -> String {
+        match self {
+            ColumnKind::Id => message.id.clone(),
+            ColumnKind::PublishTime => format_time(message.publish_time),
+            ColumnKind::ReceiveTime => format_time(message.received_at),
+            ColumnKind::OrderingKey => message.ordering_key.clone(),
+            ColumnKind::DeliveryAttempt => message
+                .delivery_attempt
+                .map(|attempt| attempt.to_string())
+                .unwrap_or_default(),
+            ColumnKind::PayloadSize => format_size(message.data.len()),
+            ColumnKind::Attribute(key) => message.attributes.get(key).cloned().unwrap_or_default(),
+            ColumnKind::JsonPointer(pointer) => match message.data_json.pointer(pointer) {
+                Some(Value::String(value)) => value.clone(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            },
+            ColumnKind::Data => message.data.clone(),
+        }
+    }
+
+    /// Compares messages by the value of the column, with missing values first.
+    pub fn compare(&self, a: &PubsubMessage, b: &PubsubMessage) -> Ordering {
+        match self {
+            ColumnKind::Id => a.id.cmp(&b.id),
+            ColumnKind::PublishTime => a.publish_time.cmp(&b.publish_time),
+            ColumnKind::ReceiveTime => a.received_at.cmp(&b.received_at),
+            ColumnKind::OrderingKey => a.ordering_key.cmp(&b.ordering_key),
+            ColumnKind::DeliveryAttempt => a.delivery_attempt.cmp(&b.delivery_attempt),
+            ColumnKind::PayloadSize => a.data.len().cmp(&b.data.len()),
+            ColumnKind::Attribute(key) => a.attributes.get(key).cmp(&b.attributes.get(key)),
+            ColumnKind::JsonPointer(pointer) => {
+                compare_json(a.data_json.pointer(pointer), b.data_json.pointer(pointer))
+            }
+            ColumnKind::Data => a.data.cmp(&b.data),
+        }
+    }
+}
+
+impl Display for ColumnKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColumnKind::Id => write!(f, "ID"),
+            ColumnKind::PublishTime => write!(f, "Publish Time"),
+            ColumnKind::ReceiveTime => write!(f, "Receive Time"),
+            ColumnKind::OrderingKey => write!(f, "Ordering Key"),
+            ColumnKind::DeliveryAttempt => write!(f, "Delivery Attempt"),
+            ColumnKind::PayloadSize => write!(f, "Payload Size"),
+            ColumnKind::Attribute(key) => write!(f, "🏷 {}", key),
+            ColumnKind::JsonPointer(pointer) => write!(f, "{}", pointer),
+            ColumnKind::Data => write!(f, "Data"),
+        }
+    }
+}
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    time.map(|time| {
+        let local_time: DateTime<Local> = time.into();
+        local_time.format("%d/%m/%Y %H:%M").to_string()
+    })
+    .unwrap_or_default()
+}
+
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Orders missing values, then `null`, numbers, and then everything else by its JSON text,
+/// matching how SQLite orders the values of the `json_pointer` function, which returns
+/// booleans and integers too large to store exactly as text.
+fn compare_json(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    fn rank(value: Option<&Value>) -> u8 {
+        match value {
+            None | Some(Value::Null) => 0,
+            Some(Value::Number(number)) if number.is_i64() || number.is_f64() => 1,
+            Some(_) => 2,
+        }
+    }
+
+    fn compare_numbers(a: &Value, b: &Value) -> Ordering {
+        match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => a
+                .as_f64()
+                .unwrap_or_default()
+                .total_cmp(&b.as_f64().unwrap_or_default()),
+        }
+    }
+
+    match (a, b, rank(a), rank(b)) {
+        (Some(a), Some(b), 1, 1) => compare_numbers(a, b),
+        (Some(a), Some(b), 2, 2) => json_text(a).cmp(&json_text(b)),
+        (_, _, a_rank, b_rank) => a_rank.cmp(&b_rank),
+    }
+}
+
+fn json_text(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pubsubman_backend::model::TopicName;
+
+    use crate::message_store::MessageStore;
+
+    use super::*;
+
+    #[test]
+    fn json_pointer_sort_matches_stored_sort() {
+        let topic_name = TopicName("projects/test/topics/test".to_string());
+        let values = [
+            r#""b""#,
+            "true",
+            "18446744073709551615",
+            "2.5",
+            r#"{"a": 1}"#,
+            "null",
+            "-3",
+            r#""true""#,
+            "false",
+            "[1]",
+            r#""A""#,
+            "9007199254740993",
+            "9007199254740992",
+            "10",
+        ];
+        let messages: Vec<_> = values
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                PubsubMessage::new(
+                    idx.to_string(),
+                    None,
+                    format!(r#"{{"v": {}}}"#, value),
+                    HashMap::new(),
+                )
+            })
+            .chain([PubsubMessage::new(
+                "missing".to_string(),
+                None,
+                "{}".to_string(),
+                HashMap::new(),
+            )])
+            .collect();
+
+        let mut store = MessageStore::open_in_memory().unwrap();
+        store.push_all(&topic_name, &messages).unwrap();
+
+        let column = ColumnKind::JsonPointer("/v".to_string());
+
+        for descending in [false, true] {
+            let settings = ColumnSettings {
+                sort: Some(ColumnSort {
+                    kind: column.clone(),
+                    descending,
+                }),
+                ..ColumnSettings::default()
+            };
+
+            let mut sorted: Vec<_> = messages.iter().collect();
+            settings.sort(&mut sorted);
+
+            let mut query = MessageQuery::default();
+            settings.apply_to(&mut query);
+            let stored = store
+                .read_page(&topic_name, &query, 0, messages.len())
+                .unwrap();
+
+            let ids = |messages: &[&PubsubMessage]| -> Vec<String> {
+                messages.iter().map(|message| message.id.clone()).collect()
+            };
+            assert_eq!(ids(&sorted), ids(&stored.iter().collect::<Vec<_>>()));
+        }
+    }
+}
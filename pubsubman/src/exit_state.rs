@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use egui::Modal;
 use pubsubman_backend::model::SubscriptionName;
 
@@ -8,11 +10,12 @@ pub struct ExitState {
     pub subscription_cleanup_state: SubscriptionCleanupState,
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Clone, PartialEq, Eq)]
 pub enum SubscriptionCleanupState {
     #[default]
     Idle,
-    Waiting,
+    /// Waiting for the results of deleting these Subscriptions.
+    Waiting(HashSet<SubscriptionName>),
     Complete,
 }
 
@@ -24,6 +27,26 @@ const MARGIN: egui::Margin = egui::Margin {
 };
 
 impl ExitState {
+    /// Completes the cleanup once every Subscription being deleted has a result, ignoring those
+    /// deleted for other reasons, such as being replaced.
+    pub fn on_subscriptions_deleted(
+        &mut self,
+        results: &[Result<SubscriptionName, SubscriptionName>],
+    ) {
+        let SubscriptionCleanupState::Waiting(pending) = &mut self.subscription_cleanup_state
+        else {
+            return;
+        };
+
+        for Ok(sub_name) | Err(sub_name) in results {
+            pending.remove(sub_name);
+        }
+
+        if pending.is_empty() {
+            self.subscription_cleanup_state = SubscriptionCleanupState::Complete;
+        }
+    }
+
     pub fn show(
         &mut self,
         ctx: &egui::Context,
@@ -39,7 +62,10 @@ impl ExitState {
             return;
         }
 
-        let title = if self.subscription_cleanup_state == SubscriptionCleanupState::Waiting {
+        let title = if matches!(
+            self.subscription_cleanup_state,
+            SubscriptionCleanupState::Waiting(_)
+        ) {
             "Deleting Subscriptions..."
         } else {
             "Confirm Quit"
@@ -54,7 +80,7 @@ impl ExitState {
                         SubscriptionCleanupState::Idle => {
                             self.render_dialog_contents(ui, sub_names, cleanup_subscriptions);
                         }
-                        SubscriptionCleanupState::Waiting => {
+                        SubscriptionCleanupState::Waiting(_) => {
                             ui.spinner();
                             ui.add_space(10.0);
                         }
@@ -89,8 +115,9 @@ impl ExitState {
         ui.horizontal(|ui| {
             ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
                 if ui.button("Delete Subscriptions").clicked() {
+                    self.subscription_cleanup_state =
+                        SubscriptionCleanupState::Waiting(sub_names.iter().cloned().collect());
                     cleanup_subscriptions(sub_names);
-                    self.subscription_cleanup_state = SubscriptionCleanupState::Waiting;
                 }
 
                 if ui.button("Skip").clicked() {
@@ -106,3 +133,33 @@ impl ExitState {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sub_name(id: &str) -> SubscriptionName {
+        SubscriptionName(format!("projects/test/subscriptions/{}", id))
+    }
+
+    #[test]
+    fn cleanup_completes_once_every_subscription_has_a_result() {
+        let mut exit_state = ExitState {
+            subscription_cleanup_state: SubscriptionCleanupState::Waiting(
+                [sub_name("a"), sub_name("b")].into_iter().collect(),
+            ),
+            ..Default::default()
+        };
+
+        // A replaced Subscription being deleted meanwhile doesn't count.
+        exit_state.on_subscriptions_deleted(&[Ok(sub_name("replaced"))]);
+        exit_state.on_subscriptions_deleted(&[Ok(sub_name("a"))]);
+        assert!(matches!(
+            exit_state.subscription_cleanup_state,
+            SubscriptionCleanupState::Waiting(_)
+        ));
+
+        exit_state.on_subscriptions_deleted(&[Err(sub_name("b"))]);
+        assert!(exit_state.subscription_cleanup_state == SubscriptionCleanupState::Complete);
+    }
+}
@@ -4,7 +4,12 @@ mod actions;
 mod app;
 mod column_settings;
 mod exit_state;
+mod message_store;
 mod notifications;
+mod retention_settings;
 mod settings;
+mod spill_store;
+mod topic_refresh;
+mod topic_schema;
 mod ui;
 pub use app::App;
@@ -0,0 +1,702 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use pubsubman_backend::model::{PubsubMessage, TopicName};
+use regex::Regex;
+use rusqlite::{Connection, OptionalExtension, functions::FunctionFlags, params, types::Value};
+
+use crate::{column_settings::ColumnKind, retention_settings::RetentionSettings, ui::Expression};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS messages (
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        topic TEXT NOT NULL,
+        id TEXT NOT NULL,
+        publish_time INTEGER,
+        data TEXT NOT NULL,
+        size INTEGER NOT NULL,
+        received_at INTEGER,
+        ordering_key TEXT NOT NULL DEFAULT '',
+        delivery_attempt INTEGER
+    );
+    CREATE INDEX IF NOT EXISTS messages_topic_seq ON messages (topic, seq);
+    CREATE INDEX IF NOT EXISTS messages_topic_publish_time ON messages (topic, publish_time);
+    CREATE INDEX IF NOT EXISTS messages_id ON messages (id);
+
+    CREATE TABLE IF NOT EXISTS message_attributes (
+        seq INTEGER NOT NULL REFERENCES messages (seq) ON DELETE CASCADE,
+        key TEXT NOT NULL,
+        value TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS message_attributes_seq ON message_attributes (seq);
+    CREATE INDEX IF NOT EXISTS message_attributes_key_value ON message_attributes (key, value);
+";
+
+/// The received messages for each Topic, stored in an SQLite database so that they survive
+/// restarts without being serialized with the rest of the app state.
+pub struct MessageStore {
+    conn: Connection,
+    /// Incremented whenever the messages of a Topic change, so that views know to re-query.
+    generations: HashMap<TopicName, u64>,
+}
+
+impl MessageStore {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Self> {
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.pragma_update(None, "foreign_keys", true)?;
+        conn.execute_batch(SCHEMA)?;
+
+        register_functions(&conn)?;
+
+        Ok(Self {
+            conn,
+            generations: HashMap::default(),
+        })
+    }
+
+    pub fn generation(&self, topic_name: &TopicName) -> u64 {
+        self.generations
+            .get(topic_name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Changes whenever the messages of any Topic change.
+    pub fn total_generation(&self) -> u64 {
+        self.generations.values().sum()
+    }
+
+    fn touch(&mut self, topic_name: &TopicName) {
+        *self.generations.entry(topic_name.clone()).or_default() += 1;
+    }
+
+    /// The Topics that have stored messages. Rather than reading every message, this seeks
+    /// from each Topic to the next by the index on the Topic.
+    pub fn topic_names(&self) -> rusqlite::Result<Vec<TopicName>> {
+        self.conn
+            .prepare_cached(
+                "WITH RECURSIVE topics (topic) AS (
+                    SELECT MIN(topic) FROM messages
+                    UNION ALL
+                    SELECT (SELECT MIN(m.topic) FROM messages m WHERE m.topic > t.topic)
+                    FROM topics t WHERE t.topic IS NOT NULL
+                )
+                SELECT topic FROM topics WHERE topic IS NOT NULL",
+            )?
+            .query_map([], |row| Ok(TopicName(row.get(0)?)))?
+            .collect()
+    }
+
+    pub fn push_all(
+        &mut self,
+        topic_name: &TopicName,
+        messages: &[PubsubMessage],
+    ) -> rusqlite::Result<()> {
+        let tx = self.conn.transaction()?;
+
+        {
+            let mut insert_message = tx.prepare_cached(
+                "INSERT INTO messages (topic, id, publish_time, data, size, received_at, ordering_key, delivery_attempt)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            let mut insert_attribute = tx.prepare_cached(
+                "INSERT INTO message_attributes (seq, key, value) VALUES (?1, ?2, ?3)",
+            )?;
+
+            for message in messages {
+                let seq = insert_message.insert(params![
+                    topic_name.0,
+                    message.id,
+                    message.publish_time.map(|t| t.timestamp_micros()),
+                    message.data,
+                    approx_size(message) as i64,
+                    message.received_at.map(|t| t.timestamp_micros()),
+                    message.ordering_key,
+                    message.delivery_attempt.map(|attempt| attempt as i64),
+                ])?;
+
+                for (key, value) in &message.attributes {
+                    insert_attribute.execute(params![seq, key, value])?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        self.touch(topic_name);
+
+        Ok(())
+    }
+
+    /// The number of messages for the Topic that match the query.
+    pub fn count(&self, topic_name: &TopicName, query: &MessageQuery) -> rusqlite::Result<usize> {
+        let (conditions, params) = query.to_sql(topic_name);
+
+        self.conn
+            .prepare_cached(&format!(
+                "SELECT COUNT(*) FROM messages m WHERE {}",
+                conditions
+            ))?
+            .query_row(rusqlite::params_from_iter(params), |row| row.get(0))
+    }
+
+    /// Reads a page of the messages for the Topic that match the query, in the order they were
+    /// received.
+    pub fn read_page(
+        &self,
+        topic_name: &TopicName,
+        query: &MessageQuery,
+        page: usize,
+        page_size: usize,
+    ) -> rusqlite::Result<Vec<PubsubMessage>> {
+        let (conditions, mut params) = query.to_sql(topic_name);
+        let (order_by, order_by_param) = query.order_by_sql(params.len());
+        params.extend(order_by_param);
+        params.push(Value::Integer(page_size as i64));
+        params.push(Value::Integer((page * page_size) as i64));
+
+        self.read_messages(
+            &format!(
+                "WHERE {} {} LIMIT ?{} OFFSET ?{}",
+                conditions,
+                order_by,
+                params.len() - 1,
+                params.len()
+            ),
+            params,
+        )
+    }
+
+    /// Reads the messages across all Topics whose value for the column equals the given value,
+    /// in order of publish time, along with the Topic each was received on.
+    pub fn read_correlated(
+        &self,
+        column: &ColumnKind,
+        value: &str,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<(TopicName, PubsubMessage)>> {
+        let (condition, mut params) = match column {
+            // Look attributes up by the index on their key and value.
+            ColumnKind::Attribute(key) => (
+                "m.seq IN (SELECT a.seq FROM message_attributes a WHERE a.key = ?1 AND a.value = ?2)"
+                    .to_string(),
+                vec![Value::Text(key.clone()), Value::Text(value.to_string())],
+            ),
+            column => {
+                let (expression, param) = column_sql(column);
+                let mut params: Vec<Value> = param.into_iter().collect();
+                params.push(Value::Text(value.to_string()));
+
+                (
+                    format!(
+                        "CAST({} AS TEXT) = ?{}",
+                        expression.replace("{0}", "?1"),
+                        params.len()
+                    ),
+                    params,
+                )
+            }
+        };
+
+        params.push(Value::Integer(limit as i64));
+
+        self.read_topic_messages(
+            &format!(
+                "WHERE {} ORDER BY m.publish_time, m.seq LIMIT ?{}",
+                condition,
+                params.len()
+            ),
+            params,
+        )
+    }
+
+    fn read_messages(
+        &self,
+        clause: &str,
+        params: Vec<Value>,
+    ) -> rusqlite::Result<Vec<PubsubMessage>> {
+        Ok(self
+            .read_topic_messages(clause, params)?
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect())
+    }
+
+    fn read_topic_messages(
+        &self,
+        clause: &str,
+        params: Vec<Value>,
+    ) -> rusqlite::Result<Vec<(TopicName, PubsubMessage)>> {
+        let mut stmt = self.conn.prepare_cached(&format!(
+            "SELECT m.id, m.publish_time, m.data,
+                (SELECT json_group_object(a.key, a.value) FROM message_attributes a WHERE a.seq = m.seq),
+                m.received_at, m.ordering_key, m.delivery_attempt, m.topic
+            FROM messages m {}",
+            clause
+        ))?;
+
+        stmt.query_map(rusqlite::params_from_iter(params), |row| {
+            let publish_time: Option<i64> = row.get(1)?;
+            let attributes: String = row.get(3)?;
+            let received_at: Option<i64> = row.get(4)?;
+            let delivery_attempt: Option<i64> = row.get(6)?;
+
+            let message = PubsubMessage {
+                received_at: received_at.and_then(DateTime::from_timestamp_micros),
+                ordering_key: row.get(5)?,
+                delivery_attempt: delivery_attempt.map(|attempt| attempt as usize),
+                ..PubsubMessage::new(
+                    row.get(0)?,
+                    publish_time.and_then(DateTime::from_timestamp_micros),
+                    row.get(2)?,
+                    serde_json::from_str(&attributes).unwrap_or_default(),
+                )
+            };
+
+            Ok((TopicName(row.get(7)?), message))
+        })?
+        .collect()
+    }
+
+    /// Removes the oldest messages for the Topic until it is within the retention limits,
+    /// returning the removed messages when they are to be spilled to disk.
+    pub fn evict(
+        &mut self,
+        topic_name: &TopicName,
+        retention: &RetentionSettings,
+        now: DateTime<Utc>,
+    ) -> rusqlite::Result<Vec<PubsubMessage>> {
+        let mut max_seq: Option<i64> = None;
+
+        if let Some(max_messages) = retention.max_messages {
+            let seq: Option<i64> = self
+                .conn
+                .prepare_cached(
+                    "SELECT seq FROM messages WHERE topic = ?1 ORDER BY seq DESC LIMIT 1 OFFSET ?2",
+                )?
+                .query_row(params![topic_name.0, max_messages as i64], |row| row.get(0))
+                .optional()?;
+
+            max_seq = max_seq.max(seq);
+        }
+
+        if let Some(max_megabytes) = retention.max_megabytes {
+            let max_bytes = max_megabytes as i64 * 1024 * 1024;
+            let seq: Option<i64> = self
+                .conn
+                .prepare_cached(
+                    "SELECT MAX(seq) FROM (
+                        SELECT seq, SUM(size) OVER (ORDER BY seq DESC) AS total
+                        FROM messages WHERE topic = ?1
+                    ) WHERE total > ?2",
+                )?
+                .query_row(params![topic_name.0, max_bytes], |row| row.get(0))?;
+
+            max_seq = max_seq.max(seq);
+        }
+
+        let cutoff = retention
+            .max_age_hours
+            .map(|max_age_hours| (now - TimeDelta::hours(max_age_hours.into())).timestamp_micros());
+
+        if max_seq.is_none() && cutoff.is_none() {
+            return Ok(vec![]);
+        }
+
+        let condition = "m.topic = ?1 AND (m.seq <= ?2 OR m.publish_time < ?3)";
+        let params = vec![
+            Value::Text(topic_name.0.clone()),
+            max_seq.map_or(Value::Null, Value::Integer),
+            cutoff.map_or(Value::Null, Value::Integer),
+        ];
+
+        // The removed messages are only needed to spill them to disk, so are otherwise not read.
+        let evicted = if retention.spill_to_disk {
+            self.read_messages(
+                &format!("WHERE {} ORDER BY m.seq", condition),
+                params.clone(),
+            )?
+        } else {
+            vec![]
+        };
+
+        let removed = self.conn.execute(
+            &format!("DELETE FROM messages AS m WHERE {}", condition),
+            rusqlite::params_from_iter(params),
+        )?;
+
+        if removed > 0 {
+            self.touch(topic_name);
+        }
+
+        Ok(evicted)
+    }
+}
+
+/// Conditions on the messages read from a [`MessageStore`], all of which must match.
+#[derive(Default, Clone, PartialEq)]
+pub struct MessageQuery {
+    conditions: Vec<String>,
+    params: Vec<Value>,
+    /// The expression to order by, where `{0}` refers to the param if any, and whether descending.
+    order_by: Option<(String, Option<Value>, bool)>,
+}
+
+impl MessageQuery {
+    /// Adds a condition, where `{0}`, `{1}` etc. refer to the given params.
+    fn push(&mut self, condition: &str, params: impl IntoIterator<Item = Value>) {
+        let mut condition = condition.to_string();
+
+        for (idx, param) in params.into_iter().enumerate() {
+            self.params.push(param);
+            // The Topic is always the first param.
+            let placeholder = format!("?{}", self.params.len() + 1);
+            condition = condition.replace(&format!("{{{}}}", idx), &placeholder);
+        }
+
+        self.conditions.push(condition);
+    }
+
+    pub fn id_contains(&mut self, text: &str) {
+        self.push(
+            "instr(lower(m.id), lower({0})) > 0",
+            [Value::Text(text.to_string())],
+        );
+    }
+
+    pub fn data_contains(&mut self, text: &str) {
+        self.push(
+            "instr(lower(m.data), lower({0})) > 0",
+            [Value::Text(text.to_string())],
+        );
+    }
+
+    pub fn id_matches_regex(&mut self, regex: &Regex) {
+        self.push("m.id REGEXP {0}", [Value::Text(regex.to_string())]);
+    }
+
+    pub fn data_matches_regex(&mut self, regex: &Regex) {
+        self.push("m.data REGEXP {0}", [Value::Text(regex.to_string())]);
+    }
+
+    /// Matches the regex against the data, ID, and attribute keys and values.
+    pub fn any_matches_regex(&mut self, regex: &Regex) {
+        self.push(
+            "(m.data REGEXP {0} OR m.id REGEXP {0} OR EXISTS (
+                SELECT 1 FROM message_attributes a
+                WHERE a.seq = m.seq AND (a.key REGEXP {0} OR a.value REGEXP {0})
+            ))",
+            [Value::Text(regex.to_string())],
+        );
+    }
+
+    /// Matches messages with the attribute, with the given value if any.
+    pub fn has_attribute(&mut self, key: &str, value: Option<&str>) {
+        match value {
+            Some(value) => self.push(
+                "EXISTS (SELECT 1 FROM message_attributes a WHERE a.seq = m.seq AND a.key = {0} AND a.value = {1})",
+                [Value::Text(key.to_string()), Value::Text(value.to_string())],
+            ),
+            None => self.push(
+                "EXISTS (SELECT 1 FROM message_attributes a WHERE a.seq = m.seq AND a.key = {0})",
+                [Value::Text(key.to_string())],
+            ),
+        }
+    }
+
+    pub fn published_from(&mut self, from: &DateTime<Utc>) {
+        self.push(
+            "m.publish_time >= {0}",
+            [Value::Integer(from.timestamp_micros())],
+        );
+    }
+
+    pub fn published_to(&mut self, to: &DateTime<Utc>) {
+        self.push(
+            "m.publish_time <= {0}",
+            [Value::Integer(to.timestamp_micros())],
+        );
+    }
+
+    /// Matches messages whose data matches the [`Expression`] source.
+    pub fn data_matches_expression(&mut self, expression: &str) {
+        self.push(
+            "expression_matches({0}, m.data)",
+            [Value::Text(expression.to_string())],
+        );
+    }
+
+    /// Matches messages whose value for the column contains the text, ignoring case.
+    pub fn column_contains(&mut self, column: &ColumnKind, text: &str) {
+        let (expression, param) = column_sql(column);
+
+        // The column's own param, if any, is numbered before the text.
+        let (condition, params) = match param {
+            Some(param) => (
+                format!(
+                    "instr(lower(CAST({} AS TEXT)), lower({{1}})) > 0",
+                    expression
+                ),
+                vec![param, Value::Text(text.to_string())],
+            ),
+            None => (
+                format!(
+                    "instr(lower(CAST({} AS TEXT)), lower({{0}})) > 0",
+                    expression
+                ),
+                vec![Value::Text(text.to_string())],
+            ),
+        };
+
+        self.push(&condition, params);
+    }
+
+    /// Orders messages by the value of the column, rather than the order they were received.
+    pub fn sort_by(&mut self, column: &ColumnKind, descending: bool) {
+        let (expression, param) = column_sql(column);
+        self.order_by = Some((expression.to_string(), param, descending));
+    }
+
+    /// The `ORDER BY` clause, numbering any param after the given number of params.
+    fn order_by_sql(&self, num_params: usize) -> (String, Option<Value>) {
+        match &self.order_by {
+            Some((expression, param, descending)) => (
+                format!(
+                    "ORDER BY {} {}, m.seq",
+                    expression.replace("{0}", &format!("?{}", num_params + 1)),
+                    if *descending { "DESC" } else { "ASC" }
+                ),
+                param.clone(),
+            ),
+            None => ("ORDER BY m.seq".to_string(), None),
+        }
+    }
+
+    fn to_sql(&self, topic_name: &TopicName) -> (String, Vec<Value>) {
+        let conditions = std::iter::once("m.topic = ?1")
+            .chain(self.conditions.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let params = std::iter::once(Value::Text(topic_name.0.clone()))
+            .chain(self.params.iter().cloned())
+            .collect();
+
+        (conditions, params)
+    }
+}
+
+/// The SQL expression for the value of a column, where `{0}` refers to the param if any.
+fn column_sql(column: &ColumnKind) -> (&'static str, Option<Value>) {
+    match column {
+        ColumnKind::Id => ("m.id", None),
+        ColumnKind::PublishTime => ("m.publish_time", None),
+        ColumnKind::ReceiveTime => ("m.received_at", None),
+        ColumnKind::OrderingKey => ("m.ordering_key", None),
+        ColumnKind::DeliveryAttempt => ("m.delivery_attempt", None),
+        ColumnKind::PayloadSize => ("length(CAST(m.data AS BLOB))", None),
+        ColumnKind::Attribute(key) => (
+            "(SELECT a.value FROM message_attributes a WHERE a.seq = m.seq AND a.key = {0})",
+            Some(Value::Text(key.clone())),
+        ),
+        ColumnKind::JsonPointer(pointer) => (
+            "json_pointer(m.data, {0})",
+            Some(Value::Text(pointer.clone())),
+        ),
+        ColumnKind::Data => ("m.data", None),
+    }
+}
+
+/// Registers the `REGEXP` operator and the `expression_matches` and `json_pointer` functions,
+/// caching the compiled pattern for each statement.
+fn register_functions(conn: &Connection) -> rusqlite::Result<()> {
+    let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+
+    conn.create_scalar_function("regexp", 2, flags, |ctx| {
+        let regex: Arc<Regex> = ctx.get_or_create_aux(0, |value| -> Result<_, BoxError> {
+            Ok(Regex::new(value.as_str()?)?)
+        })?;
+
+        Ok(ctx
+            .get_raw(1)
+            .as_str_or_null()?
+            .is_some_and(|text| regex.is_match(text)))
+    })?;
+
+    conn.create_scalar_function("expression_matches", 2, flags, |ctx| {
+        let expression: Arc<Expression> = ctx
+            .get_or_create_aux(0, |value| -> Result<_, BoxError> {
+                Ok(Expression::parse(value.as_str()?)?)
+            })?;
+
+        let data: String = ctx.get(1)?;
+        let data_json = serde_json::from_str(&data).unwrap_or(serde_json::Value::String(data));
+
+        Ok(expression.matches(&data_json))
+    })?;
+
+    conn.create_scalar_function("json_pointer", 2, flags, |ctx| {
+        let data: String = ctx.get(0)?;
+        let pointer: String = ctx.get(1)?;
+
+        let value = serde_json::from_str::<serde_json::Value>(&data)
+            .ok()
+            .and_then(|data_json| data_json.pointer(&pointer).cloned());
+
+        // Values are returned as they are shown in the column, by `ColumnKind::text`, so that
+        // they match the same text, only keeping numbers as numbers for sorting.
+        Ok(match value {
+            None | Some(serde_json::Value::Null) => Value::Null,
+            Some(serde_json::Value::Number(value)) if value.is_i64() => {
+                Value::Integer(value.as_i64().unwrap_or_default())
+            }
+            Some(serde_json::Value::Number(value)) if value.is_f64() => {
+                Value::Real(value.as_f64().unwrap_or_default())
+            }
+            Some(serde_json::Value::String(value)) => Value::Text(value),
+            // Booleans, and integers too large to store exactly.
+            Some(value) => Value::Text(value.to_string()),
+        })
+    })
+}
+
+type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// An approximation of the memory used by a message, based on the size of its contents.
+fn approx_size(message: &PubsubMessage) -> usize {
+    message.id.len()
+        + message.data.len()
+        + message
+            .attributes
+            .iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn message(id: &str, data: &str) -> PubsubMessage {
+        PubsubMessage {
+            id: id.to_string(),
+            publish_time: None,
+            data: data.to_string(),
+            data_json: serde_json::from_str(data).unwrap(),
+            attributes: HashMap::new(),
+            attributes_json: serde_json::Value::Null,
+            received_at: None,
+            ordering_key: String::new(),
+            delivery_attempt: None,
+        }
+    }
+
+    fn matching_ids(
+        store: &MessageStore,
+        topic_name: &TopicName,
+        pointer: &str,
+        text: &str,
+    ) -> Vec<String> {
+        let column = ColumnKind::JsonPointer(pointer.to_string());
+        let mut query = MessageQuery::default();
+        query.column_contains(&column, text);
+
+        let messages = store.read_page(topic_name, &query, 0, 100).unwrap();
+
+        // The SQL filter must agree with the text shown in the column.
+        for message in &messages {
+            assert!(column.text(message).contains(text));
+        }
+
+        messages.into_iter().map(|message| message.id).collect()
+    }
+
+    #[test]
+    fn json_pointer_matches_column_text() {
+        let topic_name = TopicName("projects/test/topics/test".to_string());
+        let mut store = MessageStore::open_in_memory().unwrap();
+
+        store
+            .push_all(
+                &topic_name,
+                &[
+                    message("1", r#"{"ok": true, "n": 18446744073709551615}"#),
+                    message("2", r#"{"ok": false, "n": 1.5}"#),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(matching_ids(&store, &topic_name, "/ok", "true"), ["1"]);
+        assert_eq!(matching_ids(&store, &topic_name, "/ok", "false"), ["2"]);
+        assert!(matching_ids(&store, &topic_name, "/ok", "1").is_empty());
+        assert_eq!(
+            matching_ids(&store, &topic_name, "/n", "18446744073709551615"),
+            ["1"]
+        );
+        assert_eq!(matching_ids(&store, &topic_name, "/n", "1.5"), ["2"]);
+    }
+
+    #[test]
+    fn evict_reads_messages_only_to_spill_them() {
+        let topic_name = TopicName("projects/test/topics/test".to_string());
+        let mut store = MessageStore::open_in_memory().unwrap();
+        let mut retention = RetentionSettings {
+            max_messages: Some(1),
+            ..RetentionSettings::default()
+        };
+
+        let messages = [message("1", "{}"), message("2", "{}"), message("3", "{}")];
+        store.push_all(&topic_name, &messages).unwrap();
+
+        let evicted = store.evict(&topic_name, &retention, Utc::now()).unwrap();
+        assert!(evicted.is_empty());
+
+        let query = MessageQuery::default();
+        assert_eq!(store.count(&topic_name, &query).unwrap(), 1);
+
+        store.push_all(&topic_name, &messages[..2]).unwrap();
+        retention.spill_to_disk = true;
+
+        let evicted = store.evict(&topic_name, &retention, Utc::now()).unwrap();
+        let ids: Vec<_> = evicted.into_iter().map(|message| message.id).collect();
+        assert_eq!(ids, ["3", "1"]);
+        assert_eq!(store.count(&topic_name, &query).unwrap(), 1);
+    }
+
+    #[test]
+    fn topic_names_are_distinct() {
+        let mut store = MessageStore::open_in_memory().unwrap();
+        let topic_names = ["b", "a", "c"].map(|topic| TopicName(topic.to_string()));
+
+        for topic_name in topic_names.iter().chain(&topic_names) {
+            store
+                .push_all(topic_name, &[message("1", "{}"), message("2", "{}")])
+                .unwrap();
+        }
+
+        let stored: Vec<_> = store
+            .topic_names()
+            .unwrap()
+            .into_iter()
+            .map(|topic_name| topic_name.0)
+            .collect();
+        assert_eq!(stored, ["a", "b", "c"]);
+        assert!(
+            MessageStore::open_in_memory()
+                .unwrap()
+                .topic_names()
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
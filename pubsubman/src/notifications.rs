@@ -1,9 +1,20 @@
+use std::time::{Duration, Instant};
+
 use egui_notify::Toasts;
-use pubsubman_backend::message::BackendError;
+use pubsubman_backend::{message::BackendError, model::TopicName};
+
+const PUBLISHED_NOTIFICATION_DURATION: Duration = Duration::from_secs(6);
 
 #[derive(Default)]
 pub struct Notifications {
     toasts: Toasts,
+    published: Vec<PublishedNotification>,
+}
+
+struct PublishedNotification {
+    topic_name: TopicName,
+    message_id: String,
+    created_at: Instant,
 }
 
 impl Notifications {
@@ -15,13 +26,71 @@ impl Notifications {
         });
 
         self.toasts.show(&ctx);
+        self.show_published(&ctx);
     }
 
     pub fn success(&mut self, message: String) {
         self.toasts.success(message).show_progress_bar(false);
     }
 
+    pub fn warning(&mut self, message: String) {
+        self.toasts.warning(message);
+    }
+
     pub fn error(&mut self, error: BackendError) {
         self.toasts.error(error.to_string());
     }
+
+    pub fn published(&mut self, topic_name: TopicName, message_id: String) {
+        self.published.push(PublishedNotification {
+            topic_name,
+            message_id,
+            created_at: Instant::now(),
+        });
+    }
+
+    /// Toasts from `egui_notify` are not interactive, so published notifications are rendered
+    /// separately in order to offer copying the message ID.
+    fn show_published(&mut self, ctx: &egui::Context) {
+        self.published.retain(|notification| {
+            notification.created_at.elapsed() < PUBLISHED_NOTIFICATION_DURATION
+        });
+
+        let Some(oldest) = self.published.first() else {
+            return;
+        };
+
+        ctx.request_repaint_after(
+            PUBLISHED_NOTIFICATION_DURATION.saturating_sub(oldest.created_at.elapsed()),
+        );
+
+        egui::Area::new(egui::Id::new("published_notifications"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -8.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                self.published.retain(|notification| {
+                    let mut keep = true;
+
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(egui::Color32::from_rgb(140, 230, 140), "✅");
+                            ui.label("Published message");
+                            ui.monospace(&notification.message_id);
+
+                            if ui.button("📋").on_hover_text("Copy message ID").clicked() {
+                                ui.ctx().copy_text(notification.message_id.clone());
+                            }
+
+                            if ui.button("✖").clicked() {
+                                keep = false;
+                            }
+                        })
+                        .response
+                        .on_hover_text(&notification.topic_name.0);
+                    });
+
+                    keep
+                });
+            });
+    }
 }
@@ -0,0 +1,73 @@
+/// Limits on the received messages kept in the message store for a Topic.
+/// When any limit is exceeded, the oldest messages are evicted first.
+#[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct RetentionSettings {
+    pub max_messages: Option<usize>,
+    pub max_age_hours: Option<u32>,
+    pub max_megabytes: Option<u32>,
+    /// Whether evicted messages are written to disk, where they can still be browsed.
+    pub spill_to_disk: bool,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            max_messages: Some(100_000),
+            max_age_hours: None,
+            max_megabytes: None,
+            spill_to_disk: false,
+        }
+    }
+}
+
+impl RetentionSettings {
+    /// Returns whether any of the settings were changed.
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let before = self.clone();
+
+        ui.menu_button("Retention ⏷", |ui| {
+            egui::Grid::new("retention_settings_grid")
+                .num_columns(2)
+                .spacing((8.0, 4.0))
+                .show(ui, |ui| {
+                    show_limit(
+                        ui,
+                        "Max messages",
+                        &mut self.max_messages,
+                        5000,
+                        1..=1_000_000,
+                    );
+                    show_limit(ui, "Max age (hours)", &mut self.max_age_hours, 24, 1..=8760);
+                    show_limit(ui, "Max size (MB)", &mut self.max_megabytes, 50, 1..=10_000);
+                });
+
+            ui.add_space(4.0);
+
+            ui.checkbox(&mut self.spill_to_disk, " Spill evicted messages to disk")
+                .on_hover_text("Evicted messages can still be browsed from the Archive.");
+        });
+
+        *self != before
+    }
+}
+
+fn show_limit<T: egui::emath::Numeric>(
+    ui: &mut egui::Ui,
+    label: &str,
+    limit: &mut Option<T>,
+    default: T,
+    range: std::ops::RangeInclusive<T>,
+) {
+    let mut enabled = limit.is_some();
+
+    if ui.checkbox(&mut enabled, format!(" {}", label)).changed() {
+        *limit = enabled.then_some(default);
+    }
+
+    match limit {
+        Some(value) => ui.add(egui::DragValue::new(value).range(range)),
+        None => ui.add_enabled(false, egui::DragValue::new(&mut default.clone())),
+    };
+
+    ui.end_row();
+}
@@ -1,17 +1,186 @@
+use pubsubman_backend::model::TopicName;
+
+use crate::topic_refresh::TopicRefreshSettings;
+
 #[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Settings {
     pub view: ViewSettings,
+    #[serde(default)]
+    pub layout: LayoutSettings,
+    #[serde(default)]
+    pub topic_refresh: TopicRefreshSettings,
+    #[serde(default)]
+    pub push_receiver: PushReceiverSettings,
 }
 
 #[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
 pub struct ViewSettings {
     pub show_publish_message_panel: bool,
+    /// Whether Topics that share a name prefix, such as `orders.`, are grouped together.
+    pub group_topics_by_prefix: bool,
 }
 
 impl Default for ViewSettings {
     fn default() -> Self {
         Self {
             show_publish_message_panel: true,
+            group_topics_by_prefix: true,
+        }
+    }
+}
+
+/// The local endpoint that push Subscriptions deliver messages to.
+#[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PushReceiverSettings {
+    pub port: u16,
+}
+
+impl Default for PushReceiverSettings {
+    fn default() -> Self {
+        Self { port: 8686 }
+    }
+}
+
+/// The Topics open in the central area, as tabs within panes that are shown side by side.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct LayoutSettings {
+    /// The panes, from left to right. There is always at least one, which may have no tabs.
+    pub panes: Vec<PaneSettings>,
+    /// The pane that Topics selected from the side panel are opened in.
+    pub focused_pane: usize,
+}
+
+impl Default for LayoutSettings {
+    fn default() -> Self {
+        Self {
+            panes: vec![PaneSettings::default()],
+            focused_pane: 0,
+        }
+    }
+}
+
+#[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
+pub struct PaneSettings {
+    pub tabs: Vec<TopicName>,
+    pub active_tab: usize,
+}
+
+impl PaneSettings {
+    pub fn active_topic(&self) -> Option<&TopicName> {
+        self.tabs.get(self.active_tab)
+    }
+}
+
+impl LayoutSettings {
+    /// The Topics currently shown, one for each pane with open tabs.
+    pub fn visible_topics(&self) -> impl Iterator<Item = &TopicName> {
+        self.panes.iter().filter_map(PaneSettings::active_topic)
+    }
+
+    pub fn is_topic_visible(&self, topic_name: &TopicName) -> bool {
+        self.visible_topics().any(|visible| visible == topic_name)
+    }
+
+    /// Shows the Topic, switching to its tab if it is already open, otherwise opening a new tab
+    /// in the focused pane.
+    pub fn open(&mut self, topic_name: &TopicName) {
+        for (pane_idx, pane) in self.panes.iter_mut().enumerate() {
+            if let Some(tab_idx) = pane.tabs.iter().position(|tab| tab == topic_name) {
+                pane.active_tab = tab_idx;
+                self.focused_pane = pane_idx;
+                return;
+            }
         }
+
+        self.focused_pane = self.focused_pane.min(self.panes.len().saturating_sub(1));
+
+        if self.panes.is_empty() {
+            self.panes.push(PaneSettings::default());
+        }
+
+        let pane = &mut self.panes[self.focused_pane];
+        let tab_idx = (pane.active_tab + 1).min(pane.tabs.len());
+        pane.tabs.insert(tab_idx, topic_name.clone());
+        pane.active_tab = tab_idx;
+    }
+
+    /// Closes the tab, removing its pane if it was the last tab in it.
+    pub fn close(&mut self, pane_idx: usize, tab_idx: usize) -> Option<TopicName> {
+        let pane = self.panes.get_mut(pane_idx)?;
+
+        if tab_idx >= pane.tabs.len() {
+            return None;
+        }
+
+        let topic_name = pane.tabs.remove(tab_idx);
+
+        if tab_idx < pane.active_tab || pane.active_tab >= pane.tabs.len() {
+            pane.active_tab = pane.active_tab.saturating_sub(1);
+        }
+
+        if pane.tabs.is_empty() && self.panes.len() > 1 {
+            self.panes.remove(pane_idx);
+        }
+
+        self.focused_pane = self.focused_pane.min(self.panes.len() - 1);
+
+        Some(topic_name)
+    }
+
+    /// Moves the tab into a new pane, to the right of the one it was in. The last tab of a pane
+    /// stays where it is.
+    pub fn split_right(&mut self, pane_idx: usize, tab_idx: usize) {
+        if self
+            .panes
+            .get(pane_idx)
+            .is_none_or(|pane| pane.tabs.len() < 2 || tab_idx >= pane.tabs.len())
+        {
+            return;
+        }
+
+        let Some(topic_name) = self.close(pane_idx, tab_idx) else {
+            return;
+        };
+
+        self.panes.insert(
+            pane_idx + 1,
+            PaneSettings {
+                tabs: vec![topic_name],
+                active_tab: 0,
+            },
+        );
+        self.focused_pane = pane_idx + 1;
+    }
+
+    /// Merges the pane into the one to its left.
+    pub fn merge_left(&mut self, pane_idx: usize) {
+        if pane_idx == 0 || pane_idx >= self.panes.len() {
+            return;
+        }
+
+        let pane = self.panes.remove(pane_idx);
+        let left_pane = &mut self.panes[pane_idx - 1];
+
+        left_pane.active_tab = left_pane.tabs.len() + pane.active_tab;
+        left_pane.tabs.extend(pane.tabs);
+
+        self.focused_pane = pane_idx - 1;
+    }
+
+    /// Closes any tabs of Topics that no longer exist, returning whether any were closed.
+    pub fn retain_topics(&mut self, topic_names: &[TopicName]) -> bool {
+        let mut closed_any = false;
+
+        for pane_idx in (0..self.panes.len()).rev() {
+            for tab_idx in (0..self.panes[pane_idx].tabs.len()).rev() {
+                if !topic_names.contains(&self.panes[pane_idx].tabs[tab_idx]) {
+                    closed_any |= self.close(pane_idx, tab_idx).is_some();
+                }
+            }
+        }
+
+        closed_any
     }
 }
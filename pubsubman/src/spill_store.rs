@@ -0,0 +1,165 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::PathBuf,
+};
+
+use pubsubman_backend::model::{PubsubMessage, TopicName};
+
+/// Messages evicted from the message store, stored as a JSON Lines file per Topic,
+/// so that they can still be browsed.
+pub struct SpillStore {
+    dir: PathBuf,
+    /// The number of messages in each Topic's file, counted lazily.
+    counts: HashMap<TopicName, usize>,
+}
+
+impl SpillStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            counts: HashMap::default(),
+        }
+    }
+
+    /// Percent-encodes the Topic name, keeping only lowercase letters, digits and dashes, so
+    /// that each Topic has its own file, even on case-insensitive filesystems.
+    fn path(&self, topic_name: &TopicName) -> PathBuf {
+        let file_name: String = topic_name
+            .0
+            .bytes()
+            .map(|byte| {
+                if byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-' {
+                    char::from(byte).to_string()
+                } else {
+                    format!("%{:02X}", byte)
+                }
+            })
+            .collect();
+
+        self.dir.join(format!("{}.jsonl", file_name))
+    }
+
+    pub fn append(&mut self, topic_name: &TopicName, messages: &[PubsubMessage]) -> io::Result<()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        let count = self.count(topic_name);
+
+        fs::create_dir_all(&self.dir)?;
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(topic_name))?;
+        let mut writer = BufWriter::new(file);
+
+        for message in messages {
+            serde_json::to_writer(&mut writer, message)?;
+            writer.write_all(b"\n")?;
+        }
+
+        writer.flush()?;
+
+        self.counts
+            .insert(topic_name.clone(), count + messages.len());
+
+        Ok(())
+    }
+
+    pub fn count(&mut self, topic_name: &TopicName) -> usize {
+        if let Some(count) = self.counts.get(topic_name) {
+            return *count;
+        }
+
+        let count = File::open(self.path(topic_name))
+            .map(|file| BufReader::new(file).lines().count())
+            .unwrap_or(0);
+
+        self.counts.insert(topic_name.clone(), count);
+
+        count
+    }
+
+    /// Reads a page of spilled messages, oldest first.
+    pub fn read_page(
+        &self,
+        topic_name: &TopicName,
+        page: usize,
+        page_size: usize,
+    ) -> io::Result<Vec<PubsubMessage>> {
+        let file = match File::open(self.path(topic_name)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+
+        BufReader::new(file)
+            .lines()
+            .skip(page * page_size)
+            .take(page_size)
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect()
+    }
+
+    pub fn clear(&mut self, topic_name: &TopicName) -> io::Result<()> {
+        match fs::remove_file(self.path(topic_name)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+
+        self.counts.insert(topic_name.clone(), 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_have_their_own_files() {
+        let dir = std::env::temp_dir().join(format!("pubsubman-spill-{}", std::process::id()));
+        let mut spill_store = SpillStore::new(dir.clone());
+
+        let topic_names = [
+            "projects/test/topics/orders.created",
+            "projects/test/topics/orders_created",
+            "projects/test/topics/orders%2Ecreated",
+            "projects/test/topics/Orders.created",
+        ]
+        .map(|topic_name| TopicName(topic_name.to_string()));
+
+        for (idx, topic_name) in topic_names.iter().enumerate() {
+            let message = PubsubMessage::new(idx.to_string(), None, String::new(), HashMap::new());
+            spill_store.append(topic_name, &[message]).unwrap();
+        }
+
+        spill_store.clear(&topic_names[1]).unwrap();
+
+        // Count from the files rather than those appended.
+        let mut spill_store = SpillStore::new(dir.clone());
+
+        for (idx, topic_name) in topic_names.iter().enumerate() {
+            let ids: Vec<_> = spill_store
+                .read_page(topic_name, 0, 10)
+                .unwrap()
+                .into_iter()
+                .map(|message| message.id)
+                .collect();
+
+            if idx == 1 {
+                assert!(ids.is_empty());
+                assert_eq!(spill_store.count(topic_name), 0);
+            } else {
+                assert_eq!(ids, [idx.to_string()]);
+                assert_eq!(spill_store.count(topic_name), 1);
+            }
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
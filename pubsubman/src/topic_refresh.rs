@@ -0,0 +1,289 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use pubsubman_backend::{message::FrontendMessage, model::TopicName};
+use tokio::sync::mpsc::Sender;
+
+use crate::actions::refresh_topics;
+
+/// The longest wait between retries after refreshing the Topics fails.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// How long added and removed Topics stay highlighted for.
+const HIGHLIGHT_DURATION: Duration = Duration::from_secs(60);
+
+#[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct TopicRefreshSettings {
+    /// Whether the Topics are refreshed periodically, rather than only on request.
+    pub auto_refresh: bool,
+    pub interval_secs: u64,
+}
+
+impl Default for TopicRefreshSettings {
+    fn default() -> Self {
+        Self {
+            auto_refresh: true,
+            interval_secs: 5,
+        }
+    }
+}
+
+impl TopicRefreshSettings {
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(1))
+    }
+}
+
+/// Schedules refreshes of the Topics, backing off after failures, and tracks which Topics were
+/// recently added or removed.
+#[derive(Default)]
+pub struct TopicRefresh {
+    is_pending: bool,
+    /// The number of refreshes that have failed in a row.
+    failures: u32,
+    last_completed: Option<Instant>,
+    has_loaded: bool,
+    added: HashMap<TopicName, Instant>,
+    removed: HashMap<TopicName, Instant>,
+}
+
+impl TopicRefresh {
+    pub fn refresh(&mut self, front_tx: &Sender<FrontendMessage>) {
+        if self.is_pending {
+            return;
+        }
+
+        self.is_pending = true;
+        refresh_topics(front_tx, None);
+    }
+
+    /// Also schedules a repaint for when the next refresh is due, or a highlight ends.
+    pub fn refresh_if_due(
+        &mut self,
+        ctx: &egui::Context,
+        front_tx: &Sender<FrontendMessage>,
+        settings: &TopicRefreshSettings,
+    ) {
+        self.added
+            .retain(|_, added_at| added_at.elapsed() < HIGHLIGHT_DURATION);
+        self.removed
+            .retain(|_, removed_at| removed_at.elapsed() < HIGHLIGHT_DURATION);
+
+        if self
+            .next_refresh(settings)
+            .is_some_and(|next_refresh| Instant::now() >= next_refresh)
+        {
+            self.refresh(front_tx);
+        }
+
+        let highlight_ends = self
+            .added
+            .values()
+            .chain(self.removed.values())
+            .map(|highlighted_at| *highlighted_at + HIGHLIGHT_DURATION);
+
+        if let Some(next_wake) = self
+            .next_refresh(settings)
+            .into_iter()
+            .chain(highlight_ends)
+            .min()
+        {
+            ctx.request_repaint_after(next_wake.saturating_duration_since(Instant::now()));
+        }
+    }
+
+    /// When the Topics will next be refreshed. Failed refreshes are always retried, even without
+    /// auto refresh, waiting twice as long after each consecutive failure.
+    fn next_refresh(&self, settings: &TopicRefreshSettings) -> Option<Instant> {
+        if self.is_pending {
+            return None;
+        }
+
+        let last_completed = self.last_completed?;
+
+        if self.failures > 0 {
+            let delay = settings
+                .interval()
+                .saturating_mul(2u32.saturating_pow(self.failures.min(16)))
+                .min(MAX_RETRY_DELAY);
+
+            Some(last_completed + delay)
+        } else if settings.auto_refresh {
+            Some(last_completed + settings.interval())
+        } else {
+            None
+        }
+    }
+
+    /// Records the Topics that were added or removed since the last refresh.
+    pub fn on_topics_updated(&mut self, previous: &[TopicName], current: &[TopicName]) {
+        let now = Instant::now();
+
+        self.is_pending = false;
+        self.failures = 0;
+        self.last_completed = Some(now);
+
+        if !self.has_loaded {
+            self.has_loaded = true;
+            return;
+        }
+
+        for topic_name in current.iter().filter(|topic| !previous.contains(topic)) {
+            self.removed.remove(topic_name);
+            self.added.insert(topic_name.clone(), now);
+        }
+
+        for topic_name in previous.iter().filter(|topic| !current.contains(topic)) {
+            self.added.remove(topic_name);
+            self.removed.insert(topic_name.clone(), now);
+        }
+    }
+
+    /// Returns whether this is the first failure in a row, which is worth notifying about.
+    pub fn on_refresh_failed(&mut self) -> bool {
+        self.is_pending = false;
+        self.failures += 1;
+        self.last_completed = Some(Instant::now());
+
+        self.failures == 1
+    }
+
+    pub fn is_added(&self, topic_name: &TopicName) -> bool {
+        self.added.contains_key(topic_name)
+    }
+
+    pub fn is_removed(&self, topic_name: &TopicName) -> bool {
+        self.removed.contains_key(topic_name)
+    }
+
+    /// The Topics that were recently removed, which are still listed for a while.
+    pub fn removed_topics(&self) -> impl Iterator<Item = &TopicName> {
+        self.removed.keys()
+    }
+
+    /// Stops highlighting the Topic as added, once it has been looked at.
+    pub fn mark_seen(&mut self, topic_name: &TopicName) {
+        self.added.remove(topic_name);
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        front_tx: &Sender<FrontendMessage>,
+        settings: &mut TopicRefreshSettings,
+    ) {
+        let status_text = match (self.failures, self.next_refresh(settings)) {
+            _ if self.is_pending => "Refreshing Topics…".to_string(),
+            (0, _) => match self.last_completed {
+                Some(last_completed) => {
+                    format!("Refreshed {}s ago.", last_completed.elapsed().as_secs())
+                }
+                None => "Refresh Topics.".to_string(),
+            },
+            (failures, Some(next_refresh)) => format!(
+                "Failed to refresh Topics {} time{}, retrying in {}s.",
+                failures,
+                if failures == 1 { "" } else { "s" },
+                next_refresh
+                    .saturating_duration_since(Instant::now())
+                    .as_secs()
+            ),
+            (_, None) => "Failed to refresh Topics.".to_string(),
+        };
+
+        ui.menu_button("⏷", |ui| {
+            ui.checkbox(&mut settings.auto_refresh, " Auto refresh");
+
+            ui.add_enabled_ui(settings.auto_refresh, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Every");
+                    ui.add(
+                        egui::DragValue::new(&mut settings.interval_secs)
+                            .range(1..=3600)
+                            .suffix("s"),
+                    );
+                });
+            });
+        })
+        .response
+        .on_hover_text("Refresh settings.");
+
+        let refresh_button = ui
+            .add_enabled(!self.is_pending, egui::Button::new("⟳"))
+            .on_hover_text(&status_text)
+            .on_disabled_hover_text(&status_text);
+
+        if refresh_button.clicked() {
+            self.refresh(front_tx);
+        }
+
+        let mut is_status_hovered = refresh_button.hovered();
+
+        if self.failures > 0 {
+            is_status_hovered |= ui
+                .colored_label(ui.visuals().warn_fg_color, "⚠")
+                .on_hover_text(status_text)
+                .hovered();
+        }
+
+        // Keep the seconds in the status text counting.
+        if is_status_hovered {
+            ui.ctx().request_repaint_after(Duration::from_secs(1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The delay before the next refresh, after the number of failures in a row.
+    fn retry_delay(settings: &TopicRefreshSettings, failures: u32) -> Option<Duration> {
+        let mut topic_refresh = TopicRefresh::default();
+
+        for _ in 0..failures {
+            topic_refresh.on_refresh_failed();
+        }
+
+        let last_completed = topic_refresh.last_completed?;
+        topic_refresh
+            .next_refresh(settings)
+            .map(|next_refresh| next_refresh - last_completed)
+    }
+
+    #[test]
+    fn retries_back_off_up_to_the_maximum_delay() {
+        let settings = TopicRefreshSettings::default();
+        let secs = |secs| Some(Duration::from_secs(secs));
+
+        assert_eq!(retry_delay(&settings, 1), secs(10));
+        assert_eq!(retry_delay(&settings, 2), secs(20));
+        assert_eq!(retry_delay(&settings, 5), secs(160));
+        assert_eq!(retry_delay(&settings, 6), Some(MAX_RETRY_DELAY));
+        assert_eq!(retry_delay(&settings, 100), Some(MAX_RETRY_DELAY));
+
+        let settings = TopicRefreshSettings {
+            interval_secs: u64::MAX,
+            ..settings
+        };
+        assert_eq!(retry_delay(&settings, 1), Some(MAX_RETRY_DELAY));
+    }
+
+    #[test]
+    fn failures_are_retried_without_auto_refresh() {
+        let settings = TopicRefreshSettings {
+            auto_refresh: false,
+            ..TopicRefreshSettings::default()
+        };
+
+        assert_eq!(retry_delay(&settings, 1), Some(Duration::from_secs(10)));
+
+        let mut topic_refresh = TopicRefresh::default();
+        topic_refresh.on_refresh_failed();
+        topic_refresh.on_topics_updated(&[], &[]);
+        assert_eq!(topic_refresh.next_refresh(&settings), None);
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use chrono::{DateTime, Utc};
+use pubsubman_backend::model::PubsubMessage;
+use serde_json::Value;
+
+/// A JSON Schema attached to a Topic, which published and received message data is validated against.
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct TopicSchema {
+    path: PathBuf,
+    loaded_at: DateTime<Utc>,
+    schema: Value,
+    /// Compiled lazily, since the validator itself cannot be persisted.
+    #[serde(skip)]
+    validator: OnceLock<Result<jsonschema::Validator, String>>,
+}
+
+#[derive(Clone)]
+pub struct SchemaViolation {
+    /// The JSON pointer to the invalid value, or an empty string for the root value.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.pointer, self.message)
+        }
+    }
+}
+
+impl TopicSchema {
+    pub fn load(path: PathBuf) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(&path).map_err(|err| err.to_string())?;
+        let schema: Value = serde_json::from_str(&contents).map_err(|err| err.to_string())?;
+        let validator = jsonschema::validator_for(&schema).map_err(|err| err.to_string())?;
+
+        Ok(Self {
+            path,
+            loaded_at: Utc::now(),
+            schema,
+            validator: OnceLock::from(Ok(validator)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn validate(&self, instance: &Value) -> Vec<SchemaViolation> {
+        let validator = self
+            .validator
+            .get_or_init(|| jsonschema::validator_for(&self.schema).map_err(|err| err.to_string()));
+
+        match validator {
+            Ok(validator) => validator
+                .iter_errors(instance)
+                .map(|err| SchemaViolation {
+                    pointer: err.instance_path.to_string(),
+                    message: err.to_string(),
+                })
+                .collect(),
+            Err(err) => vec![SchemaViolation {
+                pointer: String::new(),
+                message: format!("Invalid schema: {}", err),
+            }],
+        }
+    }
+}
+
+impl Hash for TopicSchema {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+        self.loaded_at.hash(state);
+    }
+}
+
+#[derive(Clone, Copy)]
+struct MessageSchemaCheck<'a> {
+    schema: &'a TopicSchema,
+    message: &'a PubsubMessage,
+}
+
+impl Hash for MessageSchemaCheck<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.schema.hash(state);
+        self.message.id.hash(state);
+    }
+}
+
+#[derive(Default)]
+struct MessageSchemaValidator;
+
+impl egui::util::cache::ComputerMut<MessageSchemaCheck<'_>, Vec<SchemaViolation>>
+    for MessageSchemaValidator
+{
+    fn compute(&mut self, check: MessageSchemaCheck<'_>) -> Vec<SchemaViolation> {
+        check.schema.validate(&check.message.data_json)
+    }
+}
+
+/// Validates the data of a received message, caching the result across frames.
+pub fn message_schema_violations(
+    ctx: &egui::Context,
+    schema: &TopicSchema,
+    message: &PubsubMessage,
+) -> Vec<SchemaViolation> {
+    type MessageSchemaValidatorCache =
+        egui::util::cache::FrameCache<Vec<SchemaViolation>, MessageSchemaValidator>;
+
+    ctx.memory_mut(|mem| {
+        mem.caches
+            .cache::<MessageSchemaValidatorCache>()
+            .get(MessageSchemaCheck { schema, message })
+    })
+}
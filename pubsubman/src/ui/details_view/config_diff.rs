@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+/// A change to a single setting, identified by its JSON pointer.
+pub struct ConfigChange {
+    pointer: String,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+/// The settings that differ between the two configurations, in order of JSON pointer.
+pub fn config_changes(
+    before: &impl serde::Serialize,
+    after: &impl serde::Serialize,
+) -> Vec<ConfigChange> {
+    let flatten_config = |config: Value| {
+        let mut settings = BTreeMap::new();
+        flatten(&mut settings, String::new(), config);
+        settings
+    };
+
+    let mut before = flatten_config(serde_json::to_value(before).unwrap_or_default());
+    let after = flatten_config(serde_json::to_value(after).unwrap_or_default());
+    let mut changes = vec![];
+
+    for (pointer, after_value) in after {
+        let before_value = before.remove(&pointer);
+
+        if before_value.as_ref() != Some(&after_value) {
+            changes.push(ConfigChange {
+                pointer,
+                before: before_value,
+                after: Some(after_value),
+            });
+        }
+    }
+
+    changes.extend(
+        before
+            .into_iter()
+            .map(|(pointer, before_value)| ConfigChange {
+                pointer,
+                before: Some(before_value),
+                after: None,
+            }),
+    );
+
+    changes.sort_by(|a, b| a.pointer.cmp(&b.pointer));
+    changes
+}
+
+/// Collects the values of the configuration by their JSON pointers. Unset values and empty
+/// objects are left out, so that setting an optional policy shows only the values it sets.
+fn flatten(settings: &mut BTreeMap<String, Value>, pointer: String, value: Value) {
+    match value {
+        Value::Null => {}
+        Value::Object(map) => {
+            for (key, value) in map {
+                let key = key.replace('~', "~0").replace('/', "~1");
+                flatten(settings, format!("{}/{}", pointer, key), value);
+            }
+        }
+        value => {
+            settings.insert(pointer, value);
+        }
+    }
+}
+
+pub fn show_config_changes(ui: &mut egui::Ui, id_salt: &str, changes: &[ConfigChange]) {
+    egui::Grid::new(("config_changes", id_salt))
+        .num_columns(3)
+        .striped(true)
+        .spacing((12.0, 4.0))
+        .show(ui, |ui| {
+            ui.strong("Setting");
+            ui.strong("Current");
+            ui.strong("New");
+            ui.end_row();
+
+            let removed_color = ui.visuals().error_fg_color;
+            let added_color = egui::Color32::from_rgb(80, 200, 120);
+
+            for change in changes {
+                ui.monospace(&change.pointer);
+                ui.colored_label(removed_color, value_text(change.before.as_ref()));
+                ui.colored_label(added_color, value_text(change.after.as_ref()));
+                ui.end_row();
+            }
+        });
+}
+
+fn value_text(value: Option<&Value>) -> String {
+    value.map_or_else(|| "(unset)".to_string(), Value::to_string)
+}
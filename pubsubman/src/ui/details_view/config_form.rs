@@ -0,0 +1,343 @@
+use std::collections::BTreeMap;
+
+use pubsubman_backend::model::{
+    CreatedSubscription, DeadLetterPolicyMetadata, RetryPolicyMetadata, SubscriptionMetadata,
+    TopicMetadata,
+};
+
+use crate::ui::validity_frame::ValidityFrame;
+
+/// The mutable settings of a Topic, being edited.
+pub struct TopicForm {
+    metadata: TopicMetadata,
+    labels: Vec<(String, String)>,
+}
+
+impl TopicForm {
+    pub fn new(metadata: &TopicMetadata) -> Self {
+        Self {
+            labels: metadata.labels.clone().into_iter().collect(),
+            metadata: metadata.clone(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, id_salt: &str) {
+        egui::Grid::new(("topic_form", id_salt))
+            .num_columns(2)
+            .spacing((8.0, 4.0))
+            .show(ui, |ui| {
+                ui.label("Labels");
+                show_labels(ui, id_salt, &mut self.labels);
+                ui.end_row();
+
+                ui.label("Message retention");
+                show_optional_secs(
+                    ui,
+                    &mut self.metadata.message_retention_duration_secs,
+                    604_800,
+                    600..=2_678_400,
+                );
+                ui.end_row();
+            });
+    }
+
+    /// The edited settings, or why they are invalid.
+    pub fn metadata(&self) -> Result<TopicMetadata, String> {
+        Ok(TopicMetadata {
+            labels: labels_map(&self.labels)?,
+            ..self.metadata.clone()
+        })
+    }
+}
+
+/// The mutable settings of the Subscription that this app streams messages from, being
+/// edited. It must keep pulling, and keep the labels that identify it as created by this app.
+pub struct SubscriptionForm {
+    metadata: SubscriptionMetadata,
+    labels: Vec<(String, String)>,
+    created_labels: Vec<(String, String)>,
+}
+
+impl SubscriptionForm {
+    pub fn new(metadata: &SubscriptionMetadata) -> Self {
+        let (created_labels, labels) = metadata
+            .labels
+            .clone()
+            .into_iter()
+            .partition(|(key, _)| CreatedSubscription::is_label(key));
+
+        Self {
+            labels,
+            created_labels,
+            metadata: metadata.clone(),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, id_salt: &str) {
+        let metadata = &mut self.metadata;
+
+        egui::Grid::new(("subscription_form", id_salt))
+            .num_columns(2)
+            .spacing((8.0, 4.0))
+            .show(ui, |ui| {
+                ui.label("Labels");
+                ui.vertical(|ui| {
+                    for (key, value) in &self.created_labels {
+                        ui.horizontal(|ui| {
+                            ui.monospace(key);
+                            ui.weak(value);
+                        })
+                        .response
+                        .on_hover_text("Identifies the Subscription as created by pubsubman.");
+                    }
+
+                    show_labels(ui, id_salt, &mut self.labels);
+                });
+                ui.end_row();
+
+                ui.label("Ack deadline");
+                ui.add(
+                    egui::DragValue::new(&mut metadata.ack_deadline_seconds)
+                        .range(10..=600)
+                        .suffix("s"),
+                );
+                ui.end_row();
+
+                ui.label("Message retention");
+                show_optional_secs(
+                    ui,
+                    &mut metadata.message_retention_duration_secs,
+                    604_800,
+                    600..=604_800,
+                );
+                ui.end_row();
+
+                ui.label("Retain acked messages");
+                ui.checkbox(&mut metadata.retain_acked_messages, "");
+                ui.end_row();
+
+                ui.label("Push endpoint");
+                ui.weak("Pull").on_hover_text(
+                    "Messages are streamed from this Subscription, so it cannot push them.",
+                );
+                ui.end_row();
+
+                ui.label("Retry policy");
+                ui.horizontal(|ui| {
+                    toggle_optional(
+                        ui,
+                        &mut metadata.retry_policy,
+                        || RetryPolicyMetadata {
+                            minimum_backoff_secs: Some(10),
+                            maximum_backoff_secs: Some(600),
+                        },
+                        "Redeliver immediately.",
+                    );
+
+                    match &mut metadata.retry_policy {
+                        Some(retry_policy) => {
+                            ui.label("Backoff");
+                            show_backoff(ui, &mut retry_policy.minimum_backoff_secs, 10);
+                            ui.label("to");
+                            show_backoff(ui, &mut retry_policy.maximum_backoff_secs, 600);
+                        }
+                        None => {
+                            ui.weak("Immediate redelivery");
+                        }
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Dead letter policy");
+                ui.horizontal(|ui| {
+                    toggle_optional(
+                        ui,
+                        &mut metadata.dead_letter_policy,
+                        || DeadLetterPolicyMetadata {
+                            dead_letter_topic: String::new(),
+                            max_delivery_attempts: 5,
+                        },
+                        "No dead lettering.",
+                    );
+
+                    match &mut metadata.dead_letter_policy {
+                        Some(dead_letter_policy) => {
+                            ui.validity_frame(
+                                !dead_letter_policy.dead_letter_topic.trim().is_empty(),
+                            )
+                            .show(ui, |ui| {
+                                ui.add(
+                                    egui::TextEdit::singleline(
+                                        &mut dead_letter_policy.dead_letter_topic,
+                                    )
+                                    .desired_width(220.0)
+                                    .hint_text("projects/{project}/topics/{topic}"),
+                                );
+                            });
+
+                            ui.label("after");
+                            ui.add(
+                                egui::DragValue::new(&mut dead_letter_policy.max_delivery_attempts)
+                                    .range(5..=100)
+                                    .suffix(" attempts"),
+                            );
+                        }
+                        None => {
+                            ui.weak("None");
+                        }
+                    }
+                });
+                ui.end_row();
+            });
+    }
+
+    /// The edited settings, or why they are invalid.
+    pub fn metadata(&self) -> Result<SubscriptionMetadata, String> {
+        let mut metadata = self.metadata.clone();
+
+        if let Some(dead_letter_policy) = &mut metadata.dead_letter_policy {
+            dead_letter_policy.dead_letter_topic =
+                dead_letter_policy.dead_letter_topic.trim().to_string();
+
+            if dead_letter_policy.dead_letter_topic.is_empty() {
+                return Err("The dead letter Topic must not be empty.".to_string());
+            }
+        }
+
+        if let Some(RetryPolicyMetadata {
+            minimum_backoff_secs: Some(minimum),
+            maximum_backoff_secs: Some(maximum),
+        }) = &metadata.retry_policy
+        {
+            if minimum > maximum {
+                return Err("The minimum retry backoff must not exceed the maximum.".to_string());
+            }
+        }
+
+        metadata.labels = labels_map(&self.labels)?;
+
+        for (key, value) in &self.created_labels {
+            if metadata.labels.insert(key.clone(), value.clone()).is_some() {
+                return Err(format!("Label key '{}' is set by pubsubman.", key));
+            }
+        }
+
+        Ok(metadata)
+    }
+}
+
+/// A checkbox that sets the value to its default when checked, and clears it when unchecked.
+fn toggle_optional<T>(
+    ui: &mut egui::Ui,
+    value: &mut Option<T>,
+    default: impl FnOnce() -> T,
+    unset_text: &str,
+) {
+    let mut enabled = value.is_some();
+
+    if ui
+        .checkbox(&mut enabled, "")
+        .on_hover_text(if enabled { unset_text } else { "Enable." })
+        .changed()
+    {
+        *value = enabled.then(default);
+    }
+}
+
+fn show_optional_secs(
+    ui: &mut egui::Ui,
+    value: &mut Option<i64>,
+    default: i64,
+    range: std::ops::RangeInclusive<i64>,
+) {
+    ui.horizontal(|ui| {
+        let mut enabled = value.is_some();
+
+        if ui.checkbox(&mut enabled, "").changed() {
+            *value = enabled.then_some(default);
+        }
+
+        match value {
+            Some(value) => ui.add(egui::DragValue::new(value).range(range).suffix("s")),
+            None => ui.add_enabled(
+                false,
+                egui::DragValue::new(&mut default.clone()).suffix("s"),
+            ),
+        };
+    });
+}
+
+fn show_backoff(ui: &mut egui::Ui, backoff_secs: &mut Option<i64>, default: i64) {
+    let value = backoff_secs.get_or_insert(default);
+    ui.add(egui::DragValue::new(value).range(0..=600).suffix("s"));
+}
+
+fn show_labels(ui: &mut egui::Ui, id_salt: &str, labels: &mut Vec<(String, String)>) {
+    ui.vertical(|ui| {
+        let mut label_idx_to_delete = None;
+
+        egui::Grid::new(("labels_form", id_salt))
+            .num_columns(3)
+            .show(ui, |ui| {
+                for (idx, (key, value)) in labels.iter_mut().enumerate() {
+                    ui.validity_frame(is_label_key_valid(key)).show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(key)
+                                .desired_width(100.0)
+                                .code_editor()
+                                .hint_text("Key"),
+                        );
+                    });
+
+                    ui.add(
+                        egui::TextEdit::singleline(value)
+                            .desired_width(100.0)
+                            .code_editor()
+                            .hint_text("Value"),
+                    );
+
+                    if ui.button("🗑").clicked() {
+                        label_idx_to_delete = Some(idx);
+                    }
+
+                    ui.end_row();
+                }
+            });
+
+        if let Some(idx) = label_idx_to_delete {
+            labels.remove(idx);
+        }
+
+        if ui.button("Add Label").clicked() {
+            labels.push(Default::default());
+        }
+    });
+}
+
+/// Label keys must start with a lowercase letter, and contain only lowercase letters,
+/// digits, underscores and dashes.
+fn is_label_key_valid(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_lowercase())
+        && key
+            .chars()
+            .all(|c| c.is_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+fn labels_map(labels: &[(String, String)]) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+
+    for (key, value) in labels {
+        if !is_label_key_valid(key) {
+            return Err(format!(
+                "Label key '{}' must start with a lowercase letter, and contain only lowercase letters, digits, underscores and dashes.",
+                key
+            ));
+        }
+
+        if map.insert(key.clone(), value.clone()).is_some() {
+            return Err(format!("Label key '{}' is used more than once.", key));
+        }
+    }
+
+    Ok(map)
+}
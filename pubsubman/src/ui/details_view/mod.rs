@@ -0,0 +1,344 @@
+use egui_json_tree::{DefaultExpand, JsonTree};
+use pubsubman_backend::{
+    message::FrontendMessage,
+    model::{
+        SubscriptionMetadata, SubscriptionName, SubscriptionUpdate, TopicMetadata, TopicName,
+        TopicUpdate,
+    },
+};
+use tokio::sync::mpsc::Sender;
+
+use crate::actions::{
+    get_subscription_metadata, get_topic_metadata, update_subscription, update_topic,
+};
+
+use self::{
+    config_diff::{ConfigChange, config_changes, show_config_changes},
+    config_form::{SubscriptionForm, TopicForm},
+};
+
+use super::json_ui::show_copyable_json;
+
+mod config_diff;
+mod config_form;
+
+/// The configuration of a Topic, and of the Subscription that this app receives its messages
+/// through, as fetched from Pub/Sub. Their mutable settings can be edited, and are only
+/// updated once the changes have been confirmed.
+#[derive(Default)]
+pub struct DetailsView {
+    pub is_open: bool,
+    topic: Option<TopicMetadata>,
+    subscription: Option<SubscriptionMetadata>,
+    is_fetching_topic: bool,
+    is_fetching_subscription: bool,
+    topic_form: Option<TopicForm>,
+    subscription_form: Option<SubscriptionForm>,
+    topic_form_error: Option<String>,
+    subscription_form_error: Option<String>,
+    pending_update: Option<PendingUpdate>,
+}
+
+enum PendingUpdate {
+    Topic(TopicUpdate, Vec<ConfigChange>),
+    Subscription(SubscriptionUpdate, Vec<ConfigChange>),
+}
+
+impl DetailsView {
+    pub fn fetch(
+        &mut self,
+        front_tx: &Sender<FrontendMessage>,
+        topic_name: &TopicName,
+        sub_name: Option<&SubscriptionName>,
+    ) {
+        self.is_fetching_topic = true;
+        get_topic_metadata(front_tx, topic_name);
+
+        if let Some(sub_name) = sub_name {
+            self.is_fetching_subscription = true;
+            get_subscription_metadata(front_tx, topic_name, sub_name);
+        }
+    }
+
+    pub fn on_topic_metadata(&mut self, metadata: TopicMetadata) {
+        self.is_fetching_topic = false;
+        self.topic = Some(metadata);
+    }
+
+    pub fn on_topic_metadata_failed(&mut self) {
+        self.is_fetching_topic = false;
+        self.topic = None;
+    }
+
+    /// The current configuration is kept when an update fails, as it was not changed.
+    pub fn on_topic_update_failed(&mut self) {
+        self.is_fetching_topic = false;
+    }
+
+    pub fn on_subscription_metadata(&mut self, metadata: SubscriptionMetadata) {
+        self.is_fetching_subscription = false;
+        self.subscription = Some(metadata);
+    }
+
+    pub fn on_subscription_metadata_failed(&mut self) {
+        self.is_fetching_subscription = false;
+        self.subscription = None;
+    }
+
+    pub fn on_subscription_update_failed(&mut self) {
+        self.is_fetching_subscription = false;
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        front_tx: &Sender<FrontendMessage>,
+        topic_name: &TopicName,
+        sub_name: Option<&SubscriptionName>,
+    ) {
+        let mut is_open = self.is_open;
+        let mut refresh = false;
+
+        egui::Window::new(format!("{} Details", topic_name.short_id()))
+            .id(egui::Id::new(("topic_details", &topic_name.0)))
+            .open(&mut is_open)
+            .default_size([400.0, 500.0])
+            .resizable(true)
+            .vscroll(true)
+            .show(ctx, |ui| {
+                let is_fetching = self.is_fetching_topic || self.is_fetching_subscription;
+                let is_editing = self.topic_form.is_some() || self.subscription_form.is_some();
+
+                if ui
+                    .add_enabled(!is_fetching && !is_editing, egui::Button::new("⟳ Refresh"))
+                    .on_hover_text("Fetch the latest configuration.")
+                    .clicked()
+                {
+                    refresh = true;
+                }
+
+                egui::CollapsingHeader::new("Topic")
+                    .id_salt(("topic_details_topic", &topic_name.0))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.monospace(&topic_name.0);
+                        self.show_topic(ui, topic_name);
+                    });
+
+                egui::CollapsingHeader::new("Subscription")
+                    .id_salt(("topic_details_subscription", &topic_name.0))
+                    .default_open(true)
+                    .show(ui, |ui| match sub_name {
+                        Some(sub_name) => {
+                            ui.monospace(&sub_name.0);
+                            self.show_subscription(ui, sub_name);
+                        }
+                        None => {
+                            ui.weak("No Subscription has been created for this Topic yet.");
+                        }
+                    });
+            });
+
+        if refresh {
+            self.fetch(front_tx, topic_name, sub_name);
+        }
+
+        self.show_confirmation(ctx, front_tx, topic_name, sub_name);
+
+        self.is_open = is_open;
+    }
+
+    fn show_topic(&mut self, ui: &mut egui::Ui, topic_name: &TopicName) {
+        let Some(form) = &mut self.topic_form else {
+            show_metadata(
+                ui,
+                ("topic_metadata", &topic_name.0),
+                self.topic.as_ref(),
+                self.is_fetching_topic,
+            );
+
+            if let Some(topic) = self.topic.as_ref().filter(|_| !self.is_fetching_topic) {
+                if ui.button("Edit").clicked() {
+                    self.topic_form = Some(TopicForm::new(topic));
+                    self.topic_form_error = None;
+                }
+            }
+
+            return;
+        };
+
+        form.show(ui, &topic_name.0);
+
+        let (review, cancel) = show_form_buttons(ui, self.topic_form_error.as_deref());
+
+        if cancel {
+            self.topic_form = None;
+        } else if let (true, Some(current)) = (review, &self.topic) {
+            match form.metadata() {
+                Ok(edited) => {
+                    let changes = config_changes(current, &edited);
+                    let update = TopicUpdate::new(current, edited);
+
+                    if update.is_empty() {
+                        self.topic_form_error = Some("Nothing has been changed.".to_string());
+                    } else {
+                        self.topic_form_error = None;
+                        self.pending_update = Some(PendingUpdate::Topic(update, changes));
+                    }
+                }
+                Err(err) => self.topic_form_error = Some(err),
+            }
+        }
+    }
+
+    fn show_subscription(&mut self, ui: &mut egui::Ui, sub_name: &SubscriptionName) {
+        let Some(form) = &mut self.subscription_form else {
+            show_metadata(
+                ui,
+                ("subscription_metadata", &sub_name.0),
+                self.subscription.as_ref(),
+                self.is_fetching_subscription,
+            );
+
+            if let Some(subscription) = self
+                .subscription
+                .as_ref()
+                .filter(|_| !self.is_fetching_subscription)
+            {
+                if ui.button("Edit").clicked() {
+                    self.subscription_form = Some(SubscriptionForm::new(subscription));
+                    self.subscription_form_error = None;
+                }
+            }
+
+            return;
+        };
+
+        form.show(ui, &sub_name.0);
+
+        let (review, cancel) = show_form_buttons(ui, self.subscription_form_error.as_deref());
+
+        if cancel {
+            self.subscription_form = None;
+        } else if let (true, Some(current)) = (review, &self.subscription) {
+            match form.metadata() {
+                Ok(edited) => {
+                    let changes = config_changes(current, &edited);
+                    let update = SubscriptionUpdate::new(current, edited);
+
+                    if update.is_empty() {
+                        self.subscription_form_error =
+                            Some("Nothing has been changed.".to_string());
+                    } else {
+                        self.subscription_form_error = None;
+                        self.pending_update = Some(PendingUpdate::Subscription(update, changes));
+                    }
+                }
+                Err(err) => self.subscription_form_error = Some(err),
+            }
+        }
+    }
+
+    /// Shows the changes to be made, applying them once confirmed.
+    fn show_confirmation(
+        &mut self,
+        ctx: &egui::Context,
+        front_tx: &Sender<FrontendMessage>,
+        topic_name: &TopicName,
+        sub_name: Option<&SubscriptionName>,
+    ) {
+        let Some(pending_update) = &self.pending_update else {
+            return;
+        };
+
+        let (title, changes) = match pending_update {
+            PendingUpdate::Topic(_, changes) => ("Update Topic?", changes),
+            PendingUpdate::Subscription(_, changes) => ("Update Subscription?", changes),
+        };
+
+        let mut apply = false;
+        let mut cancel = false;
+
+        egui::Modal::new(egui::Id::new(("config_update_modal", &topic_name.0))).show(ctx, |ui| {
+            ui.heading(title);
+            ui.add_space(8.0);
+            ui.label(topic_name.short_id());
+            ui.add_space(8.0);
+
+            show_config_changes(ui, &topic_name.0, changes);
+
+            ui.add_space(12.0);
+
+            ui.horizontal(|ui| {
+                apply = ui.button("Apply").clicked();
+                cancel = ui.button("Cancel").clicked();
+            });
+        });
+
+        if cancel {
+            self.pending_update = None;
+        }
+
+        if !apply {
+            return;
+        }
+
+        match self.pending_update.take() {
+            Some(PendingUpdate::Topic(update, _)) => {
+                self.topic_form = None;
+                self.is_fetching_topic = true;
+                update_topic(front_tx, topic_name, update);
+            }
+            Some(PendingUpdate::Subscription(update, _)) => {
+                if let Some(sub_name) = sub_name {
+                    self.subscription_form = None;
+                    self.is_fetching_subscription = true;
+                    update_subscription(front_tx, topic_name, sub_name, update);
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+/// Returns whether the changes should be reviewed, and whether editing was cancelled.
+fn show_form_buttons(ui: &mut egui::Ui, form_error: Option<&str>) -> (bool, bool) {
+    if let Some(err) = form_error {
+        ui.colored_label(ui.visuals().error_fg_color, err);
+    }
+
+    ui.horizontal(|ui| {
+        let review = ui
+            .button("Review Changes")
+            .on_hover_text("Show the changes before applying them.")
+            .clicked();
+        let cancel = ui.button("Cancel").clicked();
+
+        (review, cancel)
+    })
+    .inner
+}
+
+fn show_metadata(
+    ui: &mut egui::Ui,
+    id: impl std::hash::Hash,
+    metadata: Option<&impl serde::Serialize>,
+    is_fetching: bool,
+) {
+    if is_fetching {
+        ui.spinner();
+        return;
+    }
+
+    match metadata.and_then(|metadata| serde_json::to_value(metadata).ok()) {
+        Some(value) => {
+            JsonTree::new(id, &value)
+                .default_expand(DefaultExpand::All)
+                .on_render(show_copyable_json)
+                .show(ui);
+        }
+        None => {
+            ui.weak("Not available.");
+        }
+    }
+}
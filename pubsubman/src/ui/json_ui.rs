@@ -1,20 +1,190 @@
-use egui_json_tree::render::{DefaultRender, RenderContext};
+use egui_json_tree::{
+    JsonTreeVisuals,
+    delimiters::ExpandableDelimiter,
+    pointer::JsonPointerSegment,
+    render::{DefaultRender, RenderContext},
+};
+use regex::Regex;
 use serde_json::Value;
 
-pub fn show_json_context_menu(ui: &mut egui::Ui, context: RenderContext<'_, '_, Value>) {
-    context
+use crate::{
+    column_settings::{ColumnKind, ColumnSettings},
+    topic_schema::SchemaViolation,
+};
+
+/// A request to trace the messages that share a value, made from the context menu.
+pub type TraceRequest = (ColumnKind, String);
+
+/// Renders the JSON of received message attributes, with a context menu to copy their paths
+/// and contents, or to trace their values across Topics.
+pub fn show_attributes_json(
+    ui: &mut egui::Ui,
+    context: RenderContext<'_, '_, Value>,
+    trace_request: &mut Option<TraceRequest>,
+) {
+    let key = context
+        .pointer()
+        .to_json_pointer_string()
+        .trim_start_matches('/')
+        .replace("~1", "/")
+        .replace("~0", "~");
+
+    render_with_context_menu(
+        ui,
+        &context,
+        None,
+        Some((trace_request, ColumnKind::Attribute(key))),
+    );
+}
+
+/// Renders JSON with a context menu to copy the paths and contents of its values.
+pub fn show_copyable_json(ui: &mut egui::Ui, context: RenderContext<'_, '_, Value>) {
+    render_with_context_menu(ui, &context, None, None);
+}
+
+/// Renders with a context menu as [`show_attributes_json`], additionally outlining the values that violate
+/// the Topic's schema, and highlighting the keys and values that match the search regex.
+/// Values can be added as columns of the messages table, or traced across Topics, from the
+/// context menu.
+pub fn show_annotated_json(
+    ui: &mut egui::Ui,
+    context: RenderContext<'_, '_, Value>,
+    violations: &[SchemaViolation],
+    search_regex: Option<&Regex>,
+    column_settings: &mut ColumnSettings,
+    trace_request: &mut Option<TraceRequest>,
+) {
+    // Reserve a shape behind the rendered JSON, to paint any search highlight into.
+    let highlight_shape_idx = ui.painter().add(egui::Shape::Noop);
+
+    let trace_key = ColumnKind::JsonPointer(context.pointer().to_json_pointer_string());
+    let response = render_with_context_menu(
+        ui,
+        &context,
+        Some(column_settings),
+        Some((trace_request, trace_key)),
+    );
+
+    if let Some(regex) = search_regex {
+        let is_match = match &context {
+            RenderContext::Property(context) => match context.property {
+                JsonPointerSegment::Key(key) => regex.is_match(key),
+                JsonPointerSegment::Index(_) => false,
+            },
+            RenderContext::BaseValue(context) => regex.is_match(&context.display_value.to_string()),
+            RenderContext::ExpandableDelimiter(_) => false,
+        };
+
+        if is_match {
+            let visuals = if ui.visuals().dark_mode {
+                JsonTreeVisuals::DARK
+            } else {
+                JsonTreeVisuals::LIGHT
+            };
+
+            ui.painter().set(
+                highlight_shape_idx,
+                egui::Shape::rect_filled(
+                    response.rect,
+                    ui.visuals().widgets.hovered.corner_radius,
+                    visuals.highlight_color,
+                ),
+            );
+        }
+    }
+
+    if violations.is_empty() {
+        return;
+    }
+
+    let pointer = context.pointer().to_json_pointer_string();
+
+    // Outline the property of a violating value. The root value has no property, so outline
+    // the value itself, or its opening delimiter.
+    let should_outline = match &context {
+        RenderContext::Property(_) => true,
+        RenderContext::BaseValue(_) => pointer.is_empty(),
+        RenderContext::ExpandableDelimiter(context) => {
+            pointer.is_empty()
+                && !matches!(
+                    context.delimiter,
+                    ExpandableDelimiter::ClosingArray | ExpandableDelimiter::ClosingObject
+                )
+        }
+    };
+
+    if !should_outline {
+        return;
+    }
+
+    let messages: Vec<&str> = violations
+        .iter()
+        .filter(|violation| violation.pointer == pointer)
+        .map(|violation| violation.message.as_str())
+        .collect();
+
+    if messages.is_empty() {
+        return;
+    }
+
+    ui.painter().rect_stroke(
+        response.rect.expand(1.0),
+        ui.visuals().widgets.hovered.corner_radius,
+        egui::Stroke::new(1.0, ui.visuals().error_fg_color),
+        egui::StrokeKind::Outside,
+    );
+
+    response.on_hover_text(messages.join("\n"));
+}
+
+fn render_with_context_menu(
+    ui: &mut egui::Ui,
+    context: &RenderContext<'_, '_, Value>,
+    column_settings: Option<&mut ColumnSettings>,
+    trace: Option<(&mut Option<TraceRequest>, ColumnKind)>,
+) -> egui::Response {
+    let response = context
         .render_default(ui)
-        .on_hover_cursor(egui::CursorIcon::ContextMenu)
-        .context_menu(|ui| {
-            let pointer = context.pointer().to_json_pointer_string();
-            if !pointer.is_empty() && ui.button("Copy path").clicked() {
-                ui.ctx().copy_text(pointer);
+        .on_hover_cursor(egui::CursorIcon::ContextMenu);
+
+    response.context_menu(|ui| {
+        let pointer = context.pointer().to_json_pointer_string();
+        if !pointer.is_empty() && ui.button("Copy path").clicked() {
+            ui.ctx().copy_text(pointer.clone());
+        }
+
+        if ui.button("Copy contents").clicked() {
+            if let Ok(pretty_str) = serde_json::to_string_pretty(context.value()) {
+                ui.ctx().copy_text(pretty_str);
+            }
+        }
+
+        if let Some(column_settings) = column_settings {
+            if !pointer.is_empty() && ui.button("Add as column").clicked() {
+                column_settings.add_column(ColumnKind::JsonPointer(pointer.clone()));
+                ui.close();
             }
+        }
+
+        // Only scalar values can be traced, as that is what correlation IDs are.
+        let trace_value = match context.value() {
+            Value::String(value) => Some(value.clone()),
+            Value::Number(value) => Some(value.to_string()),
+            _ => None,
+        };
 
-            if ui.button("Copy contents").clicked() {
-                if let Ok(pretty_str) = serde_json::to_string_pretty(context.value()) {
-                    ui.ctx().copy_text(pretty_str);
-                }
+        if let (Some((trace_request, trace_key)), Some(trace_value)) = (trace, trace_value) {
+            if !pointer.is_empty()
+                && ui
+                    .button("Trace this value")
+                    .on_hover_text("Show all received messages across Topics with this value.")
+                    .clicked()
+            {
+                *trace_request = Some((trace_key, trace_value));
+                ui.close();
             }
-        });
+        }
+    });
+
+    response
 }
@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+
+use egui_json_tree::{
+    DefaultExpand, JsonTree,
+    render::{DefaultRender, RenderContext},
+};
+use pubsubman_backend::model::PubsubMessage;
+use serde_json::Value;
+
+/// A structural comparison of the data and attributes of two messages.
+pub struct MessageDiff {
+    left: PubsubMessage,
+    right: PubsubMessage,
+    data_changes: HashMap<String, Change>,
+    attributes_changes: HashMap<String, Change>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl MessageDiff {
+    pub fn new(left: PubsubMessage, right: PubsubMessage) -> Self {
+        let mut data_changes = HashMap::new();
+        diff_json(&mut data_changes, "", &left.data_json, &right.data_json);
+
+        let mut attributes_changes = HashMap::new();
+        diff_json(
+            &mut attributes_changes,
+            "",
+            &left.attributes_json,
+            &right.attributes_json,
+        );
+
+        Self {
+            left,
+            right,
+            data_changes,
+            attributes_changes,
+        }
+    }
+
+    /// Returns whether the window is still open.
+    pub fn show(&self, ctx: &egui::Context) -> bool {
+        let mut is_open = true;
+
+        egui::Window::new("Compare Messages")
+            .id(egui::Id::new((
+                "message_diff",
+                self.left.id.as_str(),
+                self.right.id.as_str(),
+            )))
+            .open(&mut is_open)
+            .default_size([900.0, 600.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                self.show_summary(ui);
+
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        ui.columns(2, |columns| {
+                            self.show_message(&mut columns[0], Side::Left);
+                            self.show_message(&mut columns[1], Side::Right);
+                        });
+                    });
+            });
+
+        is_open
+    }
+
+    fn show_summary(&self, ui: &mut egui::Ui) {
+        let count = |change| {
+            self.data_changes
+                .values()
+                .chain(self.attributes_changes.values())
+                .filter(|c| **c == change)
+                .count()
+        };
+
+        ui.horizontal(|ui| {
+            if self.data_changes.is_empty() && self.attributes_changes.is_empty() {
+                ui.label("The messages have identical data and attributes.");
+                return;
+            }
+
+            ui.colored_label(
+                change_color(ui, Change::Added),
+                format!("{} added", count(Change::Added)),
+            );
+            ui.colored_label(
+                change_color(ui, Change::Removed),
+                format!("{} removed", count(Change::Removed)),
+            );
+            ui.colored_label(
+                change_color(ui, Change::Changed),
+                format!("{} changed", count(Change::Changed)),
+            );
+        });
+    }
+
+    fn show_message(&self, ui: &mut egui::Ui, side: Side) {
+        let message = match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        };
+
+        ui.heading(&message.id);
+
+        egui::CollapsingHeader::new("Data")
+            .id_salt(("message_diff_data", message.id.as_str()))
+            .default_open(true)
+            .show(ui, |ui| {
+                JsonTree::new(
+                    ("message_diff_data_json", message.id.as_str()),
+                    &message.data_json,
+                )
+                .default_expand(DefaultExpand::All)
+                .on_render(|ui, context| {
+                    show_highlighted_json(ui, context, &self.data_changes, side)
+                })
+                .show(ui);
+            });
+
+        egui::CollapsingHeader::new("Attributes")
+            .id_salt(("message_diff_attributes", message.id.as_str()))
+            .default_open(true)
+            .show(ui, |ui| {
+                JsonTree::new(
+                    ("message_diff_attributes_json", message.id.as_str()),
+                    &message.attributes_json,
+                )
+                .default_expand(DefaultExpand::All)
+                .on_render(|ui, context| {
+                    show_highlighted_json(ui, context, &self.attributes_changes, side)
+                })
+                .show(ui);
+            });
+    }
+}
+
+/// Renders the JSON, highlighting the values that were removed or changed on the left,
+/// and those that were added or changed on the right.
+fn show_highlighted_json(
+    ui: &mut egui::Ui,
+    context: RenderContext<'_, '_, Value>,
+    changes: &HashMap<String, Change>,
+    side: Side,
+) {
+    // Reserve a shape behind the rendered JSON, to paint the highlight into.
+    let highlight_shape_idx = ui.painter().add(egui::Shape::Noop);
+
+    let response = context.render_default(ui);
+
+    let should_highlight = match &context {
+        RenderContext::Property(_) => true,
+        RenderContext::BaseValue(_) => context.pointer().to_json_pointer_string().is_empty(),
+        RenderContext::ExpandableDelimiter(_) => false,
+    };
+
+    if !should_highlight {
+        return;
+    }
+
+    let change = changes
+        .get(&context.pointer().to_json_pointer_string())
+        .copied()
+        .filter(|change| match side {
+            Side::Left => *change != Change::Added,
+            Side::Right => *change != Change::Removed,
+        });
+
+    if let Some(change) = change {
+        ui.painter().set(
+            highlight_shape_idx,
+            egui::Shape::rect_filled(
+                response.rect,
+                ui.visuals().widgets.hovered.corner_radius,
+                change_color(ui, change).gamma_multiply(0.35),
+            ),
+        );
+
+        response.on_hover_text(match change {
+            Change::Added => "Added",
+            Change::Removed => "Removed",
+            Change::Changed => "Changed",
+        });
+    }
+}
+
+fn change_color(ui: &egui::Ui, change: Change) -> egui::Color32 {
+    match change {
+        Change::Added => egui::Color32::from_rgb(80, 200, 120),
+        Change::Removed => ui.visuals().error_fg_color,
+        Change::Changed => ui.visuals().warn_fg_color,
+    }
+}
+
+/// Records the JSON pointers of the values that differ between the left and right values.
+/// Objects are compared by key and arrays by index, so only the outermost value that was added,
+/// removed or changed is recorded.
+fn diff_json(changes: &mut HashMap<String, Change>, pointer: &str, left: &Value, right: &Value) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            for (key, left_value) in left {
+                let pointer = format!("{}/{}", pointer, escape_pointer_segment(key));
+
+                match right.get(key) {
+                    Some(right_value) => diff_json(changes, &pointer, left_value, right_value),
+                    None => {
+                        changes.insert(pointer, Change::Removed);
+                    }
+                }
+            }
+
+            for key in right.keys().filter(|key| !left.contains_key(*key)) {
+                changes.insert(
+                    format!("{}/{}", pointer, escape_pointer_segment(key)),
+                    Change::Added,
+                );
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for (idx, (left_value, right_value)) in left.iter().zip(right).enumerate() {
+                diff_json(
+                    changes,
+                    &format!("{}/{}", pointer, idx),
+                    left_value,
+                    right_value,
+                );
+            }
+
+            for idx in right.len()..left.len() {
+                changes.insert(format!("{}/{}", pointer, idx), Change::Removed);
+            }
+
+            for idx in left.len()..right.len() {
+                changes.insert(format!("{}/{}", pointer, idx), Change::Added);
+            }
+        }
+        (left, right) if left != right => {
+            changes.insert(pointer.to_string(), Change::Changed);
+        }
+        _ => {}
+    }
+}
+
+fn escape_pointer_segment(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn changes(left: Value, right: Value) -> HashMap<String, Change> {
+        let mut changes = HashMap::new();
+        diff_json(&mut changes, "", &left, &right);
+        changes
+    }
+
+    #[test]
+    fn records_the_outermost_differing_pointers() {
+        let left = json!({
+            "same": {"a": 1},
+            "changed": {"a": 1, "b": 2},
+            "removed": {"a": 1},
+            "items": [1, 2, 3],
+            "type": [1],
+        });
+        let right = json!({
+            "same": {"a": 1},
+            "changed": {"a": 1, "b": 3},
+            "added": {"a": 1},
+            "items": [1, 4],
+            "type": {"0": 1},
+        });
+
+        assert_eq!(
+            changes(left, right),
+            HashMap::from([
+                ("/changed/b".to_string(), Change::Changed),
+                ("/removed".to_string(), Change::Removed),
+                ("/added".to_string(), Change::Added),
+                ("/items/1".to_string(), Change::Changed),
+                ("/items/2".to_string(), Change::Removed),
+                ("/type".to_string(), Change::Changed),
+            ])
+        );
+    }
+
+    #[test]
+    fn records_appended_array_items_as_added() {
+        assert_eq!(
+            changes(json!([1]), json!([1, 2, 3])),
+            HashMap::from([
+                ("/1".to_string(), Change::Added),
+                ("/2".to_string(), Change::Added),
+            ])
+        );
+    }
+
+    #[test]
+    fn escapes_keys_in_pointers() {
+        assert_eq!(
+            changes(json!({"a/b": 1, "c~d": 1}), json!({"a/b": 2})),
+            HashMap::from([
+                ("/a~1b".to_string(), Change::Changed),
+                ("/c~0d".to_string(), Change::Removed),
+            ])
+        );
+    }
+
+    #[test]
+    fn records_a_changed_root() {
+        assert_eq!(
+            changes(json!("a"), json!("b")),
+            HashMap::from([(String::new(), Change::Changed)])
+        );
+        assert!(changes(json!({"a": [1]}), json!({"a": [1]})).is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+use pubsubman_backend::model::{PubsubMessage, TopicName};
+
+use crate::spill_store::SpillStore;
+
+use super::{PAGE_SIZE, pager::show_page_buttons};
+
+/// Browses the messages that were evicted from the message store and spilled to disk,
+/// a page at a time.
+#[derive(Default)]
+pub struct Archive {
+    pub is_open: bool,
+    page: usize,
+    /// The page and total message count that `messages` was loaded for.
+    loaded: Option<(usize, usize)>,
+    messages: Vec<PubsubMessage>,
+    error: Option<String>,
+}
+
+impl Archive {
+    pub fn messages(&self) -> &[PubsubMessage] {
+        &self.messages
+    }
+
+    pub fn show_toggle(&mut self, ui: &mut egui::Ui, count: usize) {
+        if count == 0 && !self.is_open {
+            return;
+        }
+
+        if ui
+            .add(egui::Button::new(format!("Archive ({})", count)).selected(self.is_open))
+            .on_hover_text("Browse messages that were evicted and spilled to disk.")
+            .clicked()
+        {
+            self.is_open = !self.is_open;
+        }
+    }
+
+    pub fn show_pager(
+        &mut self,
+        ui: &mut egui::Ui,
+        selected_topic: &TopicName,
+        spill_store: &mut SpillStore,
+    ) {
+        let count = spill_store.count(selected_topic);
+
+        ui.horizontal(|ui| {
+            show_page_buttons(ui, &mut self.page, count.div_ceil(PAGE_SIZE));
+
+            ui.weak(format!("{} archived messages", count));
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Clear Archive").clicked() {
+                    if let Err(err) = spill_store.clear(selected_topic) {
+                        self.error = Some(err.to_string());
+                    }
+                }
+            });
+        });
+
+        let count = spill_store.count(selected_topic);
+
+        if self.loaded != Some((self.page, count)) {
+            match spill_store.read_page(selected_topic, self.page, PAGE_SIZE) {
+                Ok(messages) => {
+                    self.messages = messages;
+                    self.error = None;
+                }
+                Err(err) => {
+                    self.messages.clear();
+                    self.error = Some(err.to_string());
+                }
+            }
+
+            self.loaded = Some((self.page, count));
+        }
+
+        if let Some(err) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+        }
+    }
+}
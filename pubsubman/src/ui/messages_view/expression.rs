@@ -0,0 +1,398 @@
+use std::{cmp::Ordering, fmt::Display, iter::Peekable, str::CharIndices};
+
+use serde_json::Value;
+
+/// A filter expression over message data, using a subset of `jq` syntax, e.g:
+///
+/// ```text
+/// .status == "FAILED" and (.order.total > 100 or not .order.paid)
+/// ```
+///
+/// A path on its own matches when it resolves to a value other than `null` or `false`.
+#[derive(Debug, Clone)]
+pub enum Expression {
+    Or(Box<Expression>, Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Compare(Operand, ComparisonOp, Operand),
+    Truthy(Operand),
+}
+
+#[derive(Debug, Clone)]
+pub enum Operand {
+    Path(Vec<PathSegment>),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?.into_iter().peekable(),
+        };
+
+        let expression = parser.parse_or()?;
+
+        match parser.tokens.next() {
+            Some(token) => Err(format!("Unexpected {}.", token)),
+            None => Ok(expression),
+        }
+    }
+
+    pub fn matches(&self, data: &Value) -> bool {
+        match self {
+            Expression::Or(lhs, rhs) => lhs.matches(data) || rhs.matches(data),
+            Expression::And(lhs, rhs) => lhs.matches(data) && rhs.matches(data),
+            Expression::Not(expression) => !expression.matches(data),
+            Expression::Compare(lhs, op, rhs) => compare(lhs.resolve(data), *op, rhs.resolve(data)),
+            Expression::Truthy(operand) => {
+                !matches!(operand.resolve(data), Value::Null | Value::Bool(false))
+            }
+        }
+    }
+}
+
+impl Operand {
+    fn resolve<'a>(&'a self, data: &'a Value) -> &'a Value {
+        match self {
+            Operand::Literal(value) => value,
+            Operand::Path(segments) => segments
+                .iter()
+                .try_fold(data, |value, segment| match segment {
+                    PathSegment::Key(key) => value.get(key),
+                    PathSegment::Index(idx) => value.get(idx),
+                })
+                .unwrap_or(&Value::Null),
+        }
+    }
+}
+
+fn compare(lhs: &Value, op: ComparisonOp, rhs: &Value) -> bool {
+    let ordering = match (lhs, rhs) {
+        (Value::Number(lhs), Value::Number(rhs)) => lhs
+            .as_f64()
+            .zip(rhs.as_f64())
+            .and_then(|(lhs, rhs)| lhs.partial_cmp(&rhs)),
+        (Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Bool(lhs), Value::Bool(rhs)) => Some(lhs.cmp(rhs)),
+        (lhs, rhs) if lhs == rhs => Some(Ordering::Equal),
+        _ => None,
+    };
+
+    match op {
+        ComparisonOp::Eq => ordering == Some(Ordering::Equal),
+        ComparisonOp::Ne => ordering != Some(Ordering::Equal),
+        ComparisonOp::Lt => ordering == Some(Ordering::Less),
+        ComparisonOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        ComparisonOp::Gt => ordering == Some(Ordering::Greater),
+        ComparisonOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Dot,
+    LeftBracket,
+    RightBracket,
+    LeftParen,
+    RightParen,
+    Op(ComparisonOp),
+    Ident(String),
+    String(String),
+    Number(f64),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Dot => write!(f, "'.'"),
+            Token::LeftBracket => write!(f, "'['"),
+            Token::RightBracket => write!(f, "']'"),
+            Token::LeftParen => write!(f, "'('"),
+            Token::RightParen => write!(f, "')'"),
+            Token::Op(_) => write!(f, "comparison operator"),
+            Token::Ident(ident) => write!(f, "'{}'", ident),
+            Token::String(string) => write!(f, "\"{}\"", string),
+            Token::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '.' => Token::Dot,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '=' | '!' | '<' | '>' => {
+                let followed_by_eq = chars.next_if(|(_, c)| *c == '=').is_some();
+                let op = match (c, followed_by_eq) {
+                    ('=', true) => ComparisonOp::Eq,
+                    ('!', true) => ComparisonOp::Ne,
+                    ('<', false) => ComparisonOp::Lt,
+                    ('<', true) => ComparisonOp::Le,
+                    ('>', false) => ComparisonOp::Gt,
+                    ('>', true) => ComparisonOp::Ge,
+                    _ => return Err(format!("Unexpected '{}' at position {}.", c, start)),
+                };
+                Token::Op(op)
+            }
+            '"' => Token::String(tokenize_string(source, start, &mut chars)?),
+            c if c == '-' || c.is_ascii_digit() => {
+                let end = take_while(&mut chars, |c| {
+                    c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-')
+                })
+                .unwrap_or(source.len());
+                let number = &source[start..end];
+                Token::Number(
+                    number
+                        .parse()
+                        .map_err(|_| format!("Invalid number '{}'.", number))?,
+                )
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let end = take_while(&mut chars, |c| c.is_alphanumeric() || c == '_')
+                    .unwrap_or(source.len());
+                Token::Ident(source[start..end].to_string())
+            }
+            c => return Err(format!("Unexpected '{}' at position {}.", c, start)),
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Consumes characters while the predicate holds, returning the index of the first
+/// character that does not satisfy it.
+fn take_while(
+    chars: &mut Peekable<CharIndices<'_>>,
+    predicate: impl Fn(char) -> bool,
+) -> Option<usize> {
+    while chars.next_if(|(_, c)| predicate(*c)).is_some() {}
+    chars.peek().map(|(idx, _)| *idx)
+}
+
+fn tokenize_string(
+    source: &str,
+    start: usize,
+    chars: &mut Peekable<CharIndices<'_>>,
+) -> Result<String, String> {
+    let mut escaped = false;
+
+    for (idx, c) in chars.by_ref() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => {
+                return serde_json::from_str(&source[start..=idx])
+                    .map_err(|_| format!("Invalid string at position {}.", start));
+            }
+            _ => escaped = false,
+        }
+    }
+
+    Err(format!("Unterminated string at position {}.", start))
+}
+
+struct Parser {
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    fn parse_or(&mut self) -> Result<Expression, String> {
+        let mut expression = self.parse_and()?;
+
+        while self.next_if_keyword("or") {
+            expression = Expression::Or(Box::new(expression), Box::new(self.parse_and()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn parse_and(&mut self) -> Result<Expression, String> {
+        let mut expression = self.parse_unary()?;
+
+        while self.next_if_keyword("and") {
+            expression = Expression::And(Box::new(expression), Box::new(self.parse_unary()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, String> {
+        if self.next_if_keyword("not") {
+            return Ok(Expression::Not(Box::new(self.parse_unary()?)));
+        }
+
+        if self.tokens.next_if_eq(&Token::LeftParen).is_some() {
+            let expression = self.parse_or()?;
+            return match self.tokens.next() {
+                Some(Token::RightParen) => Ok(expression),
+                Some(token) => Err(format!("Expected ')' but found {}.", token)),
+                None => Err("Expected ')'.".to_string()),
+            };
+        }
+
+        let lhs = self.parse_operand()?;
+
+        match self.tokens.next_if(|token| matches!(token, Token::Op(_))) {
+            Some(Token::Op(op)) => Ok(Expression::Compare(lhs, op, self.parse_operand()?)),
+            _ => Ok(Expression::Truthy(lhs)),
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, String> {
+        match self.tokens.next() {
+            Some(Token::Dot) => self.parse_path(),
+            Some(Token::String(string)) => Ok(Operand::Literal(Value::String(string))),
+            Some(Token::Number(number)) => Ok(Operand::Literal(number.into())),
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "true" => Ok(Operand::Literal(Value::Bool(true))),
+                "false" => Ok(Operand::Literal(Value::Bool(false))),
+                "null" => Ok(Operand::Literal(Value::Null)),
+                _ => Err(format!(
+                    "Unexpected '{}'. Paths must begin with '.'.",
+                    ident
+                )),
+            },
+            Some(token) => Err(format!("Unexpected {}.", token)),
+            None => Err("Unexpected end of expression.".to_string()),
+        }
+    }
+
+    /// Parses the remainder of a path, after its leading `.`.
+    fn parse_path(&mut self) -> Result<Operand, String> {
+        let mut segments = vec![];
+        let mut expect_key = true;
+
+        loop {
+            if expect_key {
+                match self
+                    .tokens
+                    .next_if(|token| matches!(token, Token::Ident(_) | Token::String(_)))
+                {
+                    Some(Token::Ident(key) | Token::String(key)) => {
+                        segments.push(PathSegment::Key(key))
+                    }
+                    _ if segments.is_empty() => {}
+                    _ => return Err("Expected a key after '.'.".to_string()),
+                }
+            }
+
+            if self.tokens.next_if_eq(&Token::LeftBracket).is_some() {
+                match (self.tokens.next(), self.tokens.next()) {
+                    (Some(Token::Number(idx)), Some(Token::RightBracket))
+                        if idx >= 0.0 && idx.fract() == 0.0 =>
+                    {
+                        segments.push(PathSegment::Index(idx as usize))
+                    }
+                    (Some(Token::String(key)), Some(Token::RightBracket)) => {
+                        segments.push(PathSegment::Key(key))
+                    }
+                    _ => {
+                        return Err(
+                            "Expected an array index or quoted key within '[]'.".to_string()
+                        );
+                    }
+                }
+                expect_key = false;
+            } else if self.tokens.next_if_eq(&Token::Dot).is_some() {
+                expect_key = true;
+            } else {
+                return Ok(Operand::Path(segments));
+            }
+        }
+    }
+
+    fn next_if_keyword(&mut self, keyword: &str) -> bool {
+        self.tokens
+            .next_if(|token| matches!(token, Token::Ident(ident) if ident == keyword))
+            .is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn matches(source: &str, data: Value) -> bool {
+        Expression::parse(source).unwrap().matches(&data)
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let data = json!({"a": true, "b": false, "c": false});
+
+        assert!(matches(".a or .b and .c", data.clone()));
+        assert!(!matches("(.a or .b) and .c", data.clone()));
+        assert!(matches(".b and .c or .a", data));
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and() {
+        let data = json!({"a": false, "b": false});
+
+        assert!(!matches("not .a and .b", data.clone()));
+        assert!(matches("not (.a and .b)", data.clone()));
+        assert!(matches("not .a or .b", data));
+    }
+
+    #[test]
+    fn comparisons_bind_tighter_than_keywords() {
+        let data = json!({"status": "FAILED", "total": 150});
+
+        assert!(matches(
+            r#".status == "FAILED" and .total > 100 or .total < 0"#,
+            data.clone()
+        ));
+        assert!(!matches(r#"not .status == "FAILED""#, data));
+    }
+
+    #[test]
+    fn bracket_paths() {
+        let data = json!({
+            "items": [{"name": "first"}, {"name": "second"}],
+            "a key": {"b": [1, 2]},
+        });
+
+        assert!(matches(r#".items[1].name == "second""#, data.clone()));
+        assert!(matches(r#".items[0]["name"] == "first""#, data.clone()));
+        assert!(matches(r#".["a key"].b[1] == 2"#, data.clone()));
+        assert!(matches(r#"."a key".b[0] == 1"#, data.clone()));
+        assert!(!matches(".items[2]", data.clone()));
+        assert!(matches(".[0] == null", data.clone()));
+        assert!(matches(".", data));
+        assert!(matches(".[1] == 2", json!([1, 2])));
+    }
+
+    #[test]
+    fn invalid_bracket_paths() {
+        for source in [".a[-1]", ".a[1.5]", ".a[b]", ".a[0", ".a.", ".a[0]b"] {
+            assert!(Expression::parse(source).is_err(), "{}", source);
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use pubsubman_backend::model::PubsubMessage;
+
+use crate::{message_store::MessageQuery, ui::validity_frame::ValidityFrame};
+
+use super::expression::Expression;
+
+const PUBLISH_TIME_FORMAT: &str = "%d/%m/%Y %H:%M";
+const PUBLISH_DATE_FORMAT: &str = "%d/%m/%Y";
+
+/// Filters applied to received messages in addition to the search query.
+/// All filters that are set must match for a message to be shown.
+#[derive(Default)]
+pub struct MessageFilters {
+    expression: FilterInput<Expression>,
+    attributes: Vec<(String, String)>,
+    published_from: FilterInput<DateTime<Utc>>,
+    published_to: FilterInput<DateTime<Utc>>,
+}
+
+impl MessageFilters {
+    pub fn is_active(&self) -> bool {
+        self.expression.value().is_some()
+            || self.attributes.iter().any(|(key, _)| !key.is_empty())
+            || self.published_from.value().is_some()
+            || self.published_to.value().is_some()
+    }
+
+    pub fn matches(&self, message: &PubsubMessage) -> bool {
+        self.expression
+            .value()
+            .is_none_or(|expression| expression.matches(&message.data_json))
+            && self.matches_attributes(message)
+            && self.matches_publish_time(message)
+    }
+
+    fn matches_attributes(&self, message: &PubsubMessage) -> bool {
+        self.attributes
+            .iter()
+            .filter(|(key, _)| !key.is_empty())
+            .all(|(key, value)| {
+                message
+                    .attributes
+                    .get(key)
+                    .is_some_and(|attr_value| value.is_empty() || attr_value == value)
+            })
+    }
+
+    fn matches_publish_time(&self, message: &PubsubMessage) -> bool {
+        let (from, to) = (self.published_from.value(), self.published_to.value());
+
+        if from.is_none() && to.is_none() {
+            return true;
+        }
+
+        message.publish_time.is_some_and(|publish_time| {
+            from.is_none_or(|from| publish_time >= *from) && to.is_none_or(|to| publish_time <= *to)
+        })
+    }
+
+    /// Adds the filters to a query of stored messages, matching as [`MessageFilters::matches`]
+    /// does.
+    pub fn apply_to(&self, query: &mut MessageQuery) {
+        if self.expression.value().is_some() {
+            query.data_matches_expression(self.expression.text.trim());
+        }
+
+        for (key, value) in self.attributes.iter().filter(|(key, _)| !key.is_empty()) {
+            query.has_attribute(key, (!value.is_empty()).then_some(value.as_str()));
+        }
+
+        if let Some(from) = self.published_from.value() {
+            query.published_from(from);
+        }
+
+        if let Some(to) = self.published_to.value() {
+            query.published_to(to);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("message_filters_grid")
+            .num_columns(2)
+            .spacing((12.0, 6.0))
+            .show(ui, |ui| {
+                ui.label("Expression");
+                self.expression.show(
+                    ui,
+                    r#".status == "FAILED" and .total > 100"#,
+                    300.0,
+                    Expression::parse,
+                );
+                ui.end_row();
+
+                ui.label("Published");
+                ui.horizontal(|ui| {
+                    self.published_from
+                        .show(ui, "From dd/mm/yyyy hh:mm", 140.0, parse_local_time);
+                    ui.label("–");
+                    self.published_to
+                        .show(ui, "To dd/mm/yyyy hh:mm", 140.0, parse_local_time);
+                });
+                ui.end_row();
+
+                ui.label("Attributes");
+                ui.vertical(|ui| {
+                    let mut attr_idx_to_delete = None;
+
+                    for (idx, (key, value)) in self.attributes.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(key)
+                                    .desired_width(100.0)
+                                    .code_editor()
+                                    .hint_text("Key"),
+                            );
+                            ui.add(
+                                egui::TextEdit::singleline(value)
+                                    .desired_width(100.0)
+                                    .code_editor()
+                                    .hint_text("Any value"),
+                            );
+                            if ui.button("🗑").clicked() {
+                                attr_idx_to_delete = Some(idx);
+                            }
+                        });
+                    }
+
+                    if let Some(idx) = attr_idx_to_delete {
+                        self.attributes.remove(idx);
+                    }
+
+                    if ui.button("➕").clicked() {
+                        self.attributes.push(("".to_string(), "".to_string()));
+                    }
+                });
+                ui.end_row();
+            });
+    }
+}
+
+/// The text of a filter input, along with the result of parsing it.
+/// An empty or invalid input does not filter any messages.
+struct FilterInput<T> {
+    text: String,
+    parsed: Option<Result<T, String>>,
+}
+
+impl<T> Default for FilterInput<T> {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            parsed: None,
+        }
+    }
+}
+
+impl<T> FilterInput<T> {
+    fn value(&self) -> Option<&T> {
+        self.parsed.as_ref().and_then(|parsed| parsed.as_ref().ok())
+    }
+
+    fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        hint_text: &str,
+        desired_width: f32,
+        parse: impl Fn(&str) -> Result<T, String>,
+    ) {
+        let is_valid = !matches!(self.parsed, Some(Err(_)));
+
+        let response = ui
+            .validity_frame(is_valid)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.text)
+                        .desired_width(desired_width)
+                        .code_editor()
+                        .hint_text(hint_text),
+                )
+            })
+            .inner;
+
+        if response.changed() {
+            let text = self.text.trim();
+            self.parsed = (!text.is_empty()).then(|| parse(text));
+        }
+
+        if let Some(Err(err)) = &self.parsed {
+            response.on_hover_text(err);
+        }
+    }
+}
+
+fn parse_local_time(text: &str) -> Result<DateTime<Utc>, String> {
+    let naive = NaiveDateTime::parse_from_str(text, PUBLISH_TIME_FORMAT)
+        .or_else(|_| {
+            NaiveDate::parse_from_str(text, PUBLISH_DATE_FORMAT)
+                .map(|date| date.and_time(Default::default()))
+        })
+        .map_err(|_| "Expected a time in the format dd/mm/yyyy hh:mm.".to_string())?;
+
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|local_time| local_time.with_timezone(&Utc))
+        .ok_or_else(|| "This local time does not exist.".to_string())
+}
@@ -0,0 +1,907 @@
+use std::time::{Duration, Instant};
+
+use egui_extras::{Column, TableBuilder};
+use pubsubman_backend::{
+    message::FrontendMessage,
+    model::{PubsubMessage, StreamState, SubscriptionFilter, SubscriptionName, TopicName},
+};
+use regex::Regex;
+use tokio::sync::mpsc::Sender;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    actions::{pull_message_batch, stream_messages},
+    column_settings::{ColumnKind, ColumnSettings},
+    message_store::{MessageQuery, MessageStore},
+    settings::PushReceiverSettings,
+    spill_store::SpillStore,
+    topic_schema::{SchemaViolation, TopicSchema, message_schema_violations},
+};
+
+use super::MessageDiff;
+
+use self::{
+    archive::Archive,
+    filters::MessageFilters,
+    pager::show_page_buttons,
+    push_menu::show_push_menu,
+    search::{RegexScope, Search, matching_attributes},
+    stream_status::StreamStatus,
+    subscription_filter_menu::SubscriptionFilterMenu,
+};
+
+mod archive;
+mod expression;
+mod filters;
+mod pager;
+mod push_menu;
+mod search;
+mod stream_status;
+mod subscription_filter_menu;
+
+pub use self::expression::Expression;
+
+const PAGE_SIZE: usize = 1000;
+
+/// How often the messages are re-read from the [`MessageStore`] while new ones keep arriving.
+const RELOAD_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+pub struct MessagesView {
+    pub stream_messages_enabled: bool,
+    pub stream_messages_cancel_token: Option<CancellationToken>,
+    /// Whether messages delivered to the Topic's push Subscription are being received.
+    pub push_messages_enabled: bool,
+    stream_status: StreamStatus,
+    search: Search,
+    show_filters: bool,
+    filters: MessageFilters,
+    subscription_filter_menu: SubscriptionFilterMenu,
+    archive: Archive,
+    /// The page of matching messages being viewed, or `None` to follow the latest page.
+    page: Option<usize>,
+    loaded_page: Option<LoadedPage>,
+    /// The total number of stored messages, along with the store generation it was counted at,
+    /// and when.
+    total_count: Option<(u64, Instant, usize)>,
+    error: Option<String>,
+    /// The messages selected for comparison, at most two.
+    compare_selection: Vec<PubsubMessage>,
+    message_diff: Option<MessageDiff>,
+}
+
+/// A page of messages read from the [`MessageStore`], along with what it was read for.
+struct LoadedPage {
+    query: MessageQuery,
+    requested_page: Option<usize>,
+    generation: u64,
+    loaded_at: Instant,
+    page: usize,
+    matching_count: usize,
+    messages: Vec<PubsubMessage>,
+}
+
+impl MessagesView {
+    /// Whether messages are being received in the background, by streaming or being pushed.
+    pub fn is_receiving(&self) -> bool {
+        self.stream_messages_enabled || self.push_messages_enabled
+    }
+
+    pub fn stop_streaming(&mut self) {
+        self.stream_messages_enabled = false;
+        self.stream_status.clear_state();
+
+        if let Some(cancel_token) = self.stream_messages_cancel_token.take() {
+            cancel_token.cancel();
+        }
+    }
+
+    pub fn on_stream_state_changed(&mut self, state: StreamState) {
+        // Pulling a batch of messages streams them briefly too.
+        if self.stream_messages_enabled {
+            self.stream_status.on_state_changed(state);
+        }
+    }
+
+    /// The search regex, when it searches message data, to highlight matches in the data of the
+    /// selected message with.
+    pub fn data_search_regex(&self) -> Option<&Regex> {
+        match self.search.regex_scope()? {
+            RegexScope::Data | RegexScope::All => self.search.regex(),
+            RegexScope::Id => None,
+        }
+    }
+
+    pub fn on_messages_received(&mut self, count: usize) {
+        self.stream_status.on_messages_received(count);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        front_tx: &Sender<FrontendMessage>,
+        selected_topic: &TopicName,
+        sub_name: &SubscriptionName,
+        subscription_filter: Option<&SubscriptionFilter>,
+        push_sub_name: Option<&SubscriptionName>,
+        push_settings: &mut PushReceiverSettings,
+        column_settings: &mut ColumnSettings,
+        message_store: &MessageStore,
+        schema: Option<&TopicSchema>,
+        mut spill_store: Option<&mut SpillStore>,
+        on_message_id_click: impl FnMut(&PubsubMessage),
+    ) {
+        egui::TopBottomPanel::top(egui::Id::new(("messages_top_panel", &selected_topic.0)))
+            .frame(egui::Frame::side_top_panel(ui.style()).inner_margin(8.0))
+            .show_inside(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("Messages");
+
+                    ui.add_enabled_ui(!self.stream_messages_enabled, |ui| {
+                        let pull_button = ui.button("Pull");
+                        if pull_button.clicked() {
+                            pull_message_batch(
+                                front_tx,
+                                selected_topic,
+                                sub_name,
+                                &CancellationToken::new(),
+                            );
+                        }
+                        pull_button
+                            .on_hover_text(
+                                "Retrieve batch of all undelivered messages on this subscription.",
+                            )
+                            .on_disabled_hover_text("Disable Stream mode to Pull messages.");
+                    });
+
+                    let stream_mode_toggle = ui.add(
+                        egui::Button::new("Stream")
+                            .selected(self.stream_messages_enabled)
+                            .corner_radius(ui.visuals().widgets.active.corner_radius),
+                    );
+
+                    if stream_mode_toggle.clicked() {
+                        if self.stream_messages_enabled {
+                            self.stop_streaming();
+                        } else {
+                            let cancel_token = CancellationToken::new();
+
+                            stream_messages(front_tx, selected_topic, sub_name, &cancel_token);
+
+                            self.stream_messages_enabled = true;
+                            self.stream_messages_cancel_token = Some(cancel_token);
+                            self.stream_status.on_state_changed(StreamState::Connecting);
+                        }
+                    }
+
+                    stream_mode_toggle.on_hover_text(
+                        "Continuously retrieve messages delivered to this subscription.",
+                    );
+
+                    self.stream_status.show(ui, self.is_receiving());
+
+                    show_push_menu(
+                        ui,
+                        front_tx,
+                        selected_topic,
+                        push_sub_name,
+                        push_settings,
+                        &mut self.push_messages_enabled,
+                    );
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        self.subscription_filter_menu.show(
+                            ui,
+                            front_tx,
+                            selected_topic,
+                            subscription_filter,
+                        );
+
+                        if let Some(spill_store) = spill_store.as_deref_mut() {
+                            self.archive
+                                .show_toggle(ui, spill_store.count(selected_topic));
+                        }
+                    });
+                });
+            });
+
+        egui::CentralPanel::default()
+            .frame(
+                egui::Frame::central_panel(ui.style())
+                    .fill(ui.style().visuals.extreme_bg_color)
+                    .inner_margin(egui::vec2(16.0, 12.0)),
+            )
+            .show_inside(ui, |ui| {
+                let archive_spill_store = spill_store.filter(|_| self.archive.is_open);
+
+                let total_count = match &archive_spill_store {
+                    Some(_) => None,
+                    None => self.total_count(ui.ctx(), message_store, selected_topic),
+                };
+
+                if let Some(spill_store) = archive_spill_store {
+                    self.archive.show_pager(ui, selected_topic, spill_store);
+                    ui.add_space(8.0);
+                }
+
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+
+                if self.archive.is_open && self.archive.messages().is_empty() {
+                    ui.allocate_space(ui.available_size() / 2.0);
+                    ui.vertical_centered(|ui| {
+                        ui.heading("No archived messages for this Topic.");
+                    });
+                } else if !self.archive.is_open && total_count == Some(0) {
+                    ui.allocate_space(ui.available_size() / 2.0);
+                    ui.vertical_centered(|ui| {
+                        ui.heading("No messages received for this Topic.");
+                        ui.label("Pull or Stream new messages to retrieve the latest.");
+                    });
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.visuals_mut().extreme_bg_color = egui::Color32::from_gray(32);
+                        ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                            self.search.show(ui);
+
+                            let filters_text = if self.filters.is_active() {
+                                "Filters ●"
+                            } else {
+                                "Filters"
+                            };
+
+                            if ui
+                                .add(egui::Button::new(filters_text).selected(self.show_filters))
+                                .on_hover_text("Filter by expression, attributes and publish time.")
+                                .clicked()
+                            {
+                                self.show_filters = !self.show_filters;
+                            }
+
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    column_settings.show(ui);
+                                    self.show_compare_buttons(ui);
+                                },
+                            );
+                        });
+                    });
+
+                    if self.show_filters {
+                        ui.add_space(8.0);
+
+                        egui::Frame::NONE
+                            .fill(ui.style().visuals.panel_fill)
+                            .inner_margin(egui::vec2(8.0, 6.0))
+                            .corner_radius(ui.style().visuals.window_corner_radius)
+                            .show(ui, |ui| {
+                                ui.set_width(ui.available_width());
+                                self.filters.show(ui);
+
+                                if ui.button("Clear Filters").clicked() {
+                                    self.filters.clear();
+                                }
+                            });
+                    }
+
+                    // Archived messages are filtered a page at a time, whereas stored messages
+                    // are filtered by the query they are read with.
+                    let (filtered_messages, matching_count, total_count): (
+                        Vec<&PubsubMessage>,
+                        _,
+                        _,
+                    ) = if self.archive.is_open {
+                        let messages = self.archive.messages();
+                        let mut filtered_messages: Vec<&PubsubMessage> = messages
+                            .iter()
+                            .filter(|msg| {
+                                self.search.matches(msg)
+                                    && self.filters.matches(msg)
+                                    && column_settings.matches(msg)
+                            })
+                            .collect();
+
+                        column_settings.sort(&mut filtered_messages);
+
+                        let matching_count = filtered_messages.len();
+
+                        (filtered_messages, matching_count, messages.len())
+                    } else {
+                        self.load_page(ui.ctx(), message_store, selected_topic, column_settings);
+
+                        match &self.loaded_page {
+                            Some(loaded_page) => (
+                                loaded_page.messages.iter().collect(),
+                                loaded_page.matching_count,
+                                total_count.unwrap_or_default(),
+                            ),
+                            None => (vec![], 0, total_count.unwrap_or_default()),
+                        }
+                    };
+
+                    if self.search.is_active()
+                        || self.filters.is_active()
+                        || column_settings.has_filters()
+                    {
+                        ui.add_space(4.0);
+                        ui.weak(format!(
+                            "{} of {} messages match",
+                            matching_count, total_count
+                        ));
+                    }
+
+                    if !self.archive.is_open && matching_count > PAGE_SIZE {
+                        let num_pages = matching_count.div_ceil(PAGE_SIZE);
+                        let mut page = self
+                            .loaded_page
+                            .as_ref()
+                            .map(|loaded_page| loaded_page.page)
+                            .unwrap_or_default();
+
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            show_page_buttons(ui, &mut page, num_pages);
+
+                            if self.page.is_none() {
+                                ui.weak("Following latest");
+                            }
+                        });
+
+                        // Returning to the last page resumes following the latest messages.
+                        if self
+                            .loaded_page
+                            .as_ref()
+                            .is_some_and(|loaded_page| loaded_page.page != page)
+                        {
+                            self.page = (page + 1 < num_pages).then_some(page);
+                        }
+                    }
+
+                    let outer_margin = egui::Margin {
+                        top: 8,
+                        bottom: 12,
+                        ..Default::default()
+                    };
+
+                    let mut compare_toggled = None;
+
+                    egui::Frame::NONE
+                        .fill(ui.style().visuals.panel_fill)
+                        .inner_margin(egui::vec2(6.0, 3.0))
+                        .outer_margin(outer_margin)
+                        .corner_radius(ui.style().visuals.window_corner_radius)
+                        .show(ui, |ui| {
+                            compare_toggled = render_messages_table(
+                                ui,
+                                selected_topic,
+                                column_settings,
+                                filtered_messages,
+                                &self.search,
+                                schema,
+                                &self.compare_selection,
+                                on_message_id_click,
+                            );
+                        });
+
+                    if let Some(message) = compare_toggled {
+                        self.toggle_compare_selection(message);
+                    }
+                }
+            });
+
+        if let Some(message_diff) = &self.message_diff {
+            if !message_diff.show(ui.ctx()) {
+                self.message_diff = None;
+            }
+        }
+    }
+}
+
+impl MessagesView {
+    fn show_compare_buttons(&mut self, ui: &mut egui::Ui) {
+        if self.compare_selection.is_empty() {
+            return;
+        }
+
+        if ui
+            .button("✖")
+            .on_hover_text("Clear messages selected for comparison.")
+            .clicked()
+        {
+            self.compare_selection.clear();
+        }
+
+        let compare_button = ui
+            .add_enabled(
+                self.compare_selection.len() == 2,
+                egui::Button::new(format!("Compare ({}/2)", self.compare_selection.len())),
+            )
+            .on_hover_text("Show the differences between the selected messages.")
+            .on_disabled_hover_text("Right click another message ID to select it for comparison.");
+
+        if let ([left, right], true) = (self.compare_selection.as_slice(), compare_button.clicked())
+        {
+            self.message_diff = Some(MessageDiff::new(left.clone(), right.clone()));
+        }
+    }
+
+    /// Selects the message for comparison, replacing the oldest selection once two are selected,
+    /// or deselects it if it was already selected.
+    fn toggle_compare_selection(&mut self, message: PubsubMessage) {
+        if let Some(idx) = self
+            .compare_selection
+            .iter()
+            .position(|selected| selected.id == message.id)
+        {
+            self.compare_selection.remove(idx);
+        } else {
+            if self.compare_selection.len() == 2 {
+                self.compare_selection.remove(0);
+            }
+
+            self.compare_selection.push(message);
+        }
+    }
+
+    /// The total number of stored messages for the Topic, re-counted only when they change.
+    fn total_count(
+        &mut self,
+        ctx: &egui::Context,
+        message_store: &MessageStore,
+        topic_name: &TopicName,
+    ) -> Option<usize> {
+        let generation = message_store.generation(topic_name);
+
+        match self.total_count {
+            Some((counted_generation, counted_at, count))
+                if is_current(ctx, counted_generation, counted_at, generation) =>
+            {
+                Some(count)
+            }
+            _ => match message_store.count(topic_name, &MessageQuery::default()) {
+                Ok(count) => {
+                    self.total_count = Some((generation, Instant::now(), count));
+                    Some(count)
+                }
+                Err(err) => {
+                    self.error = Some(err.to_string());
+                    None
+                }
+            },
+        }
+    }
+
+    /// Reads the page of messages matching the search and filters, unless it is already loaded.
+    fn load_page(
+        &mut self,
+        ctx: &egui::Context,
+        message_store: &MessageStore,
+        topic_name: &TopicName,
+        column_settings: &ColumnSettings,
+    ) {
+        let mut query = MessageQuery::default();
+        self.search.apply_to(&mut query);
+        self.filters.apply_to(&mut query);
+        column_settings.apply_to(&mut query);
+
+        let generation = message_store.generation(topic_name);
+
+        if self.loaded_page.as_ref().is_some_and(|loaded_page| {
+            loaded_page.query == query
+                && loaded_page.requested_page == self.page
+                && is_current(
+                    ctx,
+                    loaded_page.generation,
+                    loaded_page.loaded_at,
+                    generation,
+                )
+        }) {
+            return;
+        }
+
+        let loaded_page = message_store
+            .count(topic_name, &query)
+            .and_then(|matching_count| {
+                let last_page = matching_count.saturating_sub(1) / PAGE_SIZE;
+                let page = self.page.unwrap_or(last_page).min(last_page);
+
+                let messages = message_store.read_page(topic_name, &query, page, PAGE_SIZE)?;
+
+                Ok(LoadedPage {
+                    query,
+                    requested_page: self.page,
+                    generation,
+                    loaded_at: Instant::now(),
+                    page,
+                    matching_count,
+                    messages,
+                })
+            });
+
+        match loaded_page {
+            Ok(loaded_page) => {
+                self.loaded_page = Some(loaded_page);
+                self.error = None;
+            }
+            Err(err) => {
+                self.loaded_page = None;
+                self.error = Some(err.to_string());
+            }
+        }
+    }
+}
+
+/// Whether what was read from the [`MessageStore`] at the generation can still be shown. While
+/// streaming, the stored messages change with every batch received, so they are re-read at most
+/// every [`RELOAD_INTERVAL`], rather than on every frame.
+fn is_current(
+    ctx: &egui::Context,
+    read_generation: u64,
+    read_at: Instant,
+    generation: u64,
+) -> bool {
+    if read_generation == generation {
+        return true;
+    }
+
+    let since_read = read_at.elapsed();
+
+    if since_read < RELOAD_INTERVAL {
+        ctx.request_repaint_after(RELOAD_INTERVAL - since_read);
+        return true;
+    }
+
+    false
+}
+
+/// Renders the messages, returning the message whose selection for comparison was toggled, if any.
+#[allow(clippy::too_many_arguments)]
+fn render_messages_table(
+    ui: &mut egui::Ui,
+    selected_topic: &TopicName,
+    column_settings: &mut ColumnSettings,
+    messages: Vec<&PubsubMessage>,
+    search: &Search,
+    schema: Option<&TopicSchema>,
+    compare_selection: &[PubsubMessage],
+    mut on_message_id_click: impl FnMut(&PubsubMessage),
+) -> Option<PubsubMessage> {
+    let search_regex = search.regex();
+    let regex_scope = search.regex_scope();
+    let data_highlight = search.data_highlight();
+
+    let columns: Vec<(ColumnKind, Option<f32>)> = column_settings
+        .visible_columns()
+        .map(|column| (column.kind.clone(), column.width))
+        .collect();
+
+    let row_height = ui
+        .text_style_height(&egui::TextStyle::Body)
+        .max(ui.spacing().interact_size.y);
+
+    let ctx = ui.ctx().clone();
+
+    // The table remembers column widths by index, so give each arrangement of columns its own.
+    let kinds: Vec<&ColumnKind> = columns.iter().map(|(kind, _)| kind).collect();
+
+    let mut table = TableBuilder::new(ui)
+        .id_salt((&selected_topic.0, kinds))
+        .striped(true)
+        .resizable(true)
+        .stick_to_bottom(true)
+        .auto_shrink([false, true])
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center));
+
+    for (idx, (kind, width)) in columns.iter().enumerate() {
+        let column = if idx + 1 == columns.len() {
+            // Let the last column take up all remaining space.
+            Column::remainder()
+        } else {
+            Column::initial(width.unwrap_or(match kind {
+                ColumnKind::Id => 220.0,
+                ColumnKind::Data => 300.0,
+                _ => 120.0,
+            }))
+        };
+
+        table = table.column(column.at_least(40.0).clip(true));
+    }
+
+    let mut widths = vec![];
+    let mut compare_toggled = None;
+
+    table
+        .header(row_height, |mut header| {
+            for (kind, _) in &columns {
+                header.col(|ui| {
+                    render_column_header(ui, column_settings, kind);
+                });
+            }
+        })
+        .body(|body| {
+            widths = body.widths().to_vec();
+
+            // Only the visible rows are laid out, so large pages of messages stay responsive.
+            body.rows(row_height, messages.len(), |mut row| {
+                let message = messages[row.index()];
+
+                for (kind, _) in &columns {
+                    row.col(|ui| match kind {
+                        ColumnKind::Id => {
+                            let violations = schema
+                                .map(|schema| message_schema_violations(&ctx, schema, message))
+                                .unwrap_or_default();
+
+                            render_id_cell(
+                                ui,
+                                message,
+                                search_regex,
+                                regex_scope,
+                                &violations,
+                                compare_selection
+                                    .iter()
+                                    .any(|selected| selected.id == message.id),
+                                || on_message_id_click(message),
+                                || compare_toggled = Some(message.clone()),
+                            );
+                        }
+                        ColumnKind::Data => {
+                            let preview = data_preview(message);
+                            let color = ui.visuals().text_color();
+
+                            let preview_text: egui::WidgetText = match data_highlight {
+                                Some(regex) => highlight_matches(
+                                    ui,
+                                    &preview,
+                                    regex,
+                                    egui::TextStyle::Monospace,
+                                    color,
+                                )
+                                .into(),
+                                None => egui::RichText::new(preview).monospace().into(),
+                            };
+
+                            let response = ui.add(
+                                egui::Label::new(preview_text)
+                                    .truncate()
+                                    .sense(egui::Sense::click()),
+                            );
+
+                            if response.clicked() {
+                                on_message_id_click(message);
+                            }
+                        }
+                        kind => {
+                            ui.add(
+                                egui::Label::new(
+                                    egui::RichText::new(kind.text(message)).monospace(),
+                                )
+                                .truncate(),
+                            );
+                        }
+                    });
+                }
+            });
+        });
+
+    // Remember resized columns, other than the last, which always fills the remaining space.
+    for ((kind, width), new_width) in columns
+        .iter()
+        .zip(widths)
+        .take(columns.len().saturating_sub(1))
+    {
+        if *width != Some(new_width) {
+            if let Some(column) = column_settings
+                .columns
+                .iter_mut()
+                .find(|column| column.kind == *kind)
+            {
+                column.width = Some(new_width);
+            }
+        }
+    }
+
+    compare_toggled
+}
+
+/// Renders the column title, which sorts by the column when clicked. Custom columns can also be
+/// filtered and removed from the context menu.
+fn render_column_header(
+    ui: &mut egui::Ui,
+    column_settings: &mut ColumnSettings,
+    kind: &ColumnKind,
+) {
+    let sort_indicator = match &column_settings.sort {
+        Some(sort) if sort.kind == *kind && sort.descending => " ⏷",
+        Some(sort) if sort.kind == *kind => " ⏶",
+        _ => "",
+    };
+
+    let Some(column) = column_settings
+        .columns
+        .iter_mut()
+        .find(|column| column.kind == *kind)
+    else {
+        return;
+    };
+
+    let filter_indicator = if column.filter.is_empty() { "" } else { " ●" };
+
+    let response = ui
+        .add(
+            egui::Label::new(
+                egui::RichText::new(format!("{}{}{}", kind, filter_indicator, sort_indicator))
+                    .strong(),
+            )
+            .truncate()
+            .sense(egui::Sense::click()),
+        )
+        .on_hover_text(if kind.is_custom() {
+            "Click to sort by this column, or right click to filter it."
+        } else {
+            "Click to sort by this column."
+        });
+
+    let mut remove_column = false;
+
+    if kind.is_custom() {
+        response.context_menu(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("Filter");
+                ui.add(
+                    egui::TextEdit::singleline(&mut column.filter)
+                        .desired_width(150.0)
+                        .code_editor()
+                        .hint_text("Value contains"),
+                );
+            });
+
+            if ui.button("Remove column").clicked() {
+                remove_column = true;
+                ui.close();
+            }
+        });
+    }
+
+    if response.clicked() {
+        column_settings.toggle_sort(kind);
+    }
+
+    if remove_column {
+        column_settings.remove_column(kind);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_id_cell(
+    ui: &mut egui::Ui,
+    message: &PubsubMessage,
+    search_regex: Option<&regex::Regex>,
+    regex_scope: Option<RegexScope>,
+    violations: &[SchemaViolation],
+    is_compare_selected: bool,
+    on_click: impl FnOnce(),
+    on_toggle_compare: impl FnOnce(),
+) {
+    let id_text = match (search_regex, regex_scope) {
+        (Some(regex), Some(RegexScope::Id | RegexScope::All)) => highlight_matches(
+            ui,
+            &message.id,
+            regex,
+            egui::TextStyle::Body,
+            ui.visuals().hyperlink_color,
+        )
+        .into(),
+        _ => egui::WidgetText::from(&message.id),
+    };
+
+    let response = ui.link(id_text);
+
+    if response.clicked() {
+        on_click();
+    }
+
+    response.context_menu(|ui| {
+        let toggle_text = if is_compare_selected {
+            "Deselect for comparison"
+        } else {
+            "Select for comparison"
+        };
+
+        if ui.button(toggle_text).clicked() {
+            on_toggle_compare();
+            ui.close();
+        }
+    });
+
+    if is_compare_selected {
+        ui.label("⇄").on_hover_text("Selected for comparison.");
+    }
+
+    if let (Some(regex), Some(RegexScope::All)) = (search_regex, regex_scope) {
+        let attributes = matching_attributes(regex, message);
+
+        if !attributes.is_empty() {
+            let attributes_text = attributes
+                .iter()
+                .map(|(key, value)| format!("{} = {}", key, value))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            ui.label("🏷")
+                .on_hover_text(format!("Matching attributes:\n{}", attributes_text));
+        }
+    }
+
+    if !violations.is_empty() {
+        let violations_text = violations
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+            .on_hover_text(format!("Violates schema:\n{}", violations_text));
+    }
+}
+
+/// The maximum number of characters shown in a preview of message data.
+const DATA_PREVIEW_LENGTH: usize = 500;
+
+/// The message data on a single line, with JSON data compacted.
+pub(super) fn data_preview(message: &PubsubMessage) -> String {
+    let preview = match &message.data_json {
+        serde_json::Value::String(data) if *data == message.data => {
+            data.split_whitespace().collect::<Vec<_>>().join(" ")
+        }
+        data_json => data_json.to_string(),
+    };
+
+    match preview.char_indices().nth(DATA_PREVIEW_LENGTH) {
+        Some((idx, _)) => format!("{}…", &preview[..idx]),
+        None => preview,
+    }
+}
+
+/// Lays out the text with the regex matches highlighted.
+fn highlight_matches(
+    ui: &egui::Ui,
+    text: &str,
+    regex: &regex::Regex,
+    text_style: egui::TextStyle,
+    color: egui::Color32,
+) -> egui::text::LayoutJob {
+    let font_id = text_style.resolve(ui.style());
+    let highlight_color = ui.visuals().selection.bg_fill;
+
+    let mut job = egui::text::LayoutJob::default();
+    let mut last_end = 0;
+
+    for m in regex.find_iter(text).filter(|m| !m.is_empty()) {
+        job.append(
+            &text[last_end..m.start()],
+            0.0,
+            egui::TextFormat::simple(font_id.clone(), color),
+        );
+        job.append(
+            m.as_str(),
+            0.0,
+            egui::TextFormat {
+                background: highlight_color,
+                ..egui::TextFormat::simple(font_id.clone(), color)
+            },
+        );
+        last_end = m.end();
+    }
+
+    job.append(
+        &text[last_end..],
+        0.0,
+        egui::TextFormat::simple(font_id, color),
+    );
+
+    job
+}
@@ -0,0 +1,19 @@
+/// Renders the previous and next page buttons either side of the current page.
+/// `page` is zero-based, and is clamped to the number of pages.
+pub fn show_page_buttons(ui: &mut egui::Ui, page: &mut usize, num_pages: usize) {
+    let num_pages = num_pages.max(1);
+    *page = (*page).min(num_pages - 1);
+
+    if ui.add_enabled(*page > 0, egui::Button::new("◀")).clicked() {
+        *page -= 1;
+    }
+
+    ui.label(format!("Page {} of {}", *page + 1, num_pages));
+
+    if ui
+        .add_enabled(*page + 1 < num_pages, egui::Button::new("▶"))
+        .clicked()
+    {
+        *page += 1;
+    }
+}
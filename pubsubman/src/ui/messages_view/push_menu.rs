@@ -0,0 +1,86 @@
+use pubsubman_backend::{
+    message::FrontendMessage,
+    model::{SubscriptionName, TopicName},
+};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    actions::{create_push_subscription, receive_push_messages, stop_push_messages},
+    settings::PushReceiverSettings,
+};
+
+/// Toggles receiving messages through a push Subscription, which delivers them to a local
+/// endpoint rather than being pulled, to see the payloads that push-based services receive.
+pub fn show_push_menu(
+    ui: &mut egui::Ui,
+    front_tx: &Sender<FrontendMessage>,
+    selected_topic: &TopicName,
+    push_sub_name: Option<&SubscriptionName>,
+    settings: &mut PushReceiverSettings,
+    push_messages_enabled: &mut bool,
+) {
+    let button_text = if *push_messages_enabled {
+        "Push ● ⏷"
+    } else {
+        "Push ⏷"
+    };
+
+    ui.menu_button(button_text, |ui| {
+        ui.label("Receive messages pushed to a local endpoint by a dedicated push Subscription.");
+
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Port:");
+            ui.add(
+                egui::DragValue::new(&mut settings.port)
+                    .speed(0.0)
+                    .update_while_editing(false)
+                    .range(1024..=u16::MAX),
+            )
+            .on_hover_text("Shared by all push Subscriptions.");
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Endpoint:");
+            ui.monospace(format!("http://localhost:{}/push", settings.port));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Subscription:");
+            match push_sub_name {
+                Some(sub_name) => ui.monospace(&sub_name.0),
+                None => ui.weak("Created when first enabled."),
+            };
+        });
+
+        ui.add_space(4.0);
+
+        if ui
+            .checkbox(push_messages_enabled, " Receive pushed messages")
+            .changed()
+        {
+            match (*push_messages_enabled, push_sub_name) {
+                (true, Some(sub_name)) => {
+                    receive_push_messages(front_tx, selected_topic, sub_name, settings.port);
+                }
+                (true, None) => {
+                    create_push_subscription(front_tx, selected_topic, settings.port);
+                }
+                (false, Some(sub_name)) => {
+                    stop_push_messages(front_tx, sub_name);
+                }
+                (false, None) => {}
+            }
+        }
+
+        if *push_messages_enabled && push_sub_name.is_none() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Creating push Subscription…");
+            });
+        }
+    })
+    .response
+    .on_hover_text("Receive messages through a push Subscription.");
+}
@@ -0,0 +1,221 @@
+use std::fmt::Display;
+
+use pubsubman_backend::model::PubsubMessage;
+use regex::Regex;
+
+use crate::{message_store::MessageQuery, ui::validity_frame::ValidityFrame};
+
+#[derive(Default)]
+pub struct Search {
+    query: String,
+    mode: SearchMode,
+    regex_scope: RegexScope,
+    /// The compiled query, when in [`SearchMode::Regex`].
+    regex: Option<Result<Regex, String>>,
+    /// The query as a case insensitive regex, when in [`SearchMode::Data`], to highlight matches.
+    data_query_regex: Option<Regex>,
+}
+
+impl Search {
+    pub fn is_active(&self) -> bool {
+        match self.mode {
+            SearchMode::Data | SearchMode::Id => !self.query.is_empty(),
+            SearchMode::Regex => self.regex().is_some(),
+        }
+    }
+
+    /// The compiled regex to highlight matches with, if a valid one has been entered.
+    pub fn regex(&self) -> Option<&Regex> {
+        self.regex.as_ref().and_then(|regex| regex.as_ref().ok())
+    }
+
+    /// The scope that matches are highlighted in, for a valid regex.
+    pub fn regex_scope(&self) -> Option<RegexScope> {
+        self.regex().map(|_| self.regex_scope)
+    }
+
+    pub fn matches(&self, message: &PubsubMessage) -> bool {
+        match self.mode {
+            SearchMode::Data => contains_ignore_case(&message.data, &self.query),
+            SearchMode::Id => contains_ignore_case(&message.id, &self.query),
+            SearchMode::Regex => match self.regex() {
+                Some(regex) => match self.regex_scope {
+                    RegexScope::Data => regex.is_match(&message.data),
+                    RegexScope::Id => regex.is_match(&message.id),
+                    RegexScope::All => {
+                        regex.is_match(&message.data)
+                            || regex.is_match(&message.id)
+                            || !matching_attributes(regex, message).is_empty()
+                    }
+                },
+                // An empty or invalid regex does not filter any messages.
+                None => true,
+            },
+        }
+    }
+
+    /// Adds the search to a query of stored messages, matching as [`Search::matches`] does.
+    pub fn apply_to(&self, query: &mut MessageQuery) {
+        if !self.is_active() {
+            return;
+        }
+
+        match self.mode {
+            SearchMode::Data => query.data_contains(&self.query),
+            SearchMode::Id => query.id_contains(&self.query),
+            SearchMode::Regex => {
+                if let Some(regex) = self.regex() {
+                    match self.regex_scope {
+                        RegexScope::Data => query.data_matches_regex(regex),
+                        RegexScope::Id => query.id_matches_regex(regex),
+                        RegexScope::All => query.any_matches_regex(regex),
+                    }
+                }
+            }
+        }
+    }
+
+    /// The regex to highlight matches in message data with, if any.
+    pub fn data_highlight(&self) -> Option<&Regex> {
+        match (self.mode, self.regex_scope()) {
+            (SearchMode::Data, _) => self.data_query_regex.as_ref(),
+            (SearchMode::Regex, Some(RegexScope::Data | RegexScope::All)) => self.regex(),
+            _ => None,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        let is_valid = !matches!(self.regex, Some(Err(_)));
+
+        let response = ui
+            .validity_frame(is_valid)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .desired_width(125.0)
+                        .hint_text("Search"),
+                )
+            })
+            .inner;
+
+        if let Some(Err(err)) = &self.regex {
+            response.on_hover_text(err);
+        }
+
+        let mode_changed = egui::ComboBox::from_id_salt("search_mode_combo_box")
+            .selected_text(format!("{}", self.mode))
+            .width(50.0)
+            .show_ui(ui, |ui| {
+                [SearchMode::Data, SearchMode::Id, SearchMode::Regex]
+                    .into_iter()
+                    .fold(false, |changed, mode| {
+                        ui.selectable_value(&mut self.mode, mode, mode.to_string())
+                            .changed()
+                            || changed
+                    })
+            })
+            .inner
+            .unwrap_or_default();
+
+        if self.mode == SearchMode::Regex {
+            egui::ComboBox::from_id_salt("regex_scope_combo_box")
+                .selected_text(format!("{}", self.regex_scope))
+                .width(70.0)
+                .show_ui(ui, |ui| {
+                    for scope in [RegexScope::Data, RegexScope::Id, RegexScope::All] {
+                        ui.selectable_value(&mut self.regex_scope, scope, scope.to_string());
+                    }
+                });
+        }
+
+        ui.visuals_mut().widgets.inactive.weak_bg_fill = egui::Color32::from_gray(32);
+
+        if ui.button("✖").clicked() && !self.query.is_empty() {
+            self.query.clear();
+        }
+
+        if mode_changed || self.regex_is_stale() {
+            self.regex = (self.mode == SearchMode::Regex && !self.query.is_empty())
+                .then(|| Regex::new(&self.query).map_err(|err| err.to_string()));
+        }
+
+        let data_query_pattern = format!("(?i){}", regex::escape(&self.query));
+
+        if self.mode != SearchMode::Data || self.query.is_empty() {
+            self.data_query_regex = None;
+        } else if self
+            .data_query_regex
+            .as_ref()
+            .is_none_or(|regex| regex.as_str() != data_query_pattern)
+        {
+            self.data_query_regex = Regex::new(&data_query_pattern).ok();
+        }
+    }
+
+    fn regex_is_stale(&self) -> bool {
+        match &self.regex {
+            Some(Ok(regex)) => regex.as_str() != self.query,
+            Some(Err(_)) => true,
+            None => self.mode == SearchMode::Regex && !self.query.is_empty(),
+        }
+    }
+}
+
+/// The attributes of a message whose key or value match the regex.
+pub fn matching_attributes<'a>(
+    regex: &Regex,
+    message: &'a PubsubMessage,
+) -> Vec<(&'a String, &'a String)> {
+    let mut attributes: Vec<_> = message
+        .attributes
+        .iter()
+        .filter(|(key, value)| regex.is_match(key) || regex.is_match(value))
+        .collect();
+
+    attributes.sort();
+    attributes
+}
+
+fn contains_ignore_case(source: &str, query: &str) -> bool {
+    source
+        .to_ascii_lowercase()
+        .contains(&query.to_ascii_lowercase())
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+enum SearchMode {
+    #[default]
+    Data,
+    Id,
+    Regex,
+}
+
+impl Display for SearchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchMode::Data => write!(f, "Data"),
+            SearchMode::Id => write!(f, "ID"),
+            SearchMode::Regex => write!(f, "Regex"),
+        }
+    }
+}
+
+/// The message fields that a regex search is applied to.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegexScope {
+    #[default]
+    Data,
+    Id,
+    /// Data, ID, and attribute keys and values.
+    All,
+}
+
+impl Display for RegexScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegexScope::Data => write!(f, "Data"),
+            RegexScope::Id => write!(f, "ID"),
+            RegexScope::All => write!(f, "All fields"),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use pubsubman_backend::model::StreamState;
+
+/// The period the message rate is averaged over.
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// The state of the stream of messages, and how quickly messages are being received.
+#[derive(Default)]
+pub struct StreamStatus {
+    /// The state of the stream, along with when it changed to it.
+    state: Option<(StreamState, Instant)>,
+    /// When messages were received, along with how many.
+    received: VecDeque<(Instant, usize)>,
+}
+
+impl StreamStatus {
+    pub fn on_state_changed(&mut self, state: StreamState) {
+        self.state = Some((state, Instant::now()));
+    }
+
+    pub fn on_messages_received(&mut self, count: usize) {
+        let now = Instant::now();
+
+        while self
+            .received
+            .front()
+            .is_some_and(|(received_at, _)| now.duration_since(*received_at) > RATE_WINDOW)
+        {
+            self.received.pop_front();
+        }
+
+        self.received.push_back((now, count));
+    }
+
+    pub fn clear_state(&mut self) {
+        self.state = None;
+    }
+
+    /// The messages received per second, averaged over the [`RATE_WINDOW`].
+    fn rate(&self) -> f64 {
+        let count: usize = self
+            .received
+            .iter()
+            .filter(|(received_at, _)| received_at.elapsed() <= RATE_WINDOW)
+            .map(|(_, count)| count)
+            .sum();
+
+        count as f64 / RATE_WINDOW.as_secs_f64()
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui, is_receiving: bool) {
+        if let Some((state, changed_at)) = self.state {
+            // Keep the countdown to resuming going.
+            if matches!(state, StreamState::Resuming { .. }) {
+                ui.ctx().request_repaint_after(Duration::from_secs(1));
+            }
+
+            let (text, color, hover_text) = match state {
+                StreamState::Connecting => (
+                    "Connecting…".to_string(),
+                    ui.visuals().weak_text_color(),
+                    "Opening the stream of messages.".to_string(),
+                ),
+                StreamState::Streaming => (
+                    "● Streaming".to_string(),
+                    egui::Color32::from_rgb(80, 200, 120),
+                    "Receiving messages as they are delivered.".to_string(),
+                ),
+                StreamState::Stalled => (
+                    "⏸ Stalled".to_string(),
+                    ui.visuals().warn_fg_color,
+                    "Messages are arriving faster than they can be shown, so they are being held back."
+                        .to_string(),
+                ),
+                StreamState::Resuming { attempt, delay } => (
+                    format!(
+                        "⟳ Resuming in {}s",
+                        delay.saturating_sub(changed_at.elapsed()).as_secs()
+                    ),
+                    ui.visuals().warn_fg_color,
+                    format!(
+                        "The stream ended unexpectedly {} time{} in a row, and will be resumed.",
+                        attempt,
+                        if attempt == 1 { "" } else { "s" }
+                    ),
+                ),
+            };
+
+            ui.colored_label(color, text).on_hover_text(hover_text);
+        }
+
+        if is_receiving {
+            // Keep the rate falling as received messages leave the window.
+            if self
+                .received
+                .back()
+                .is_some_and(|(received_at, _)| received_at.elapsed() <= RATE_WINDOW)
+            {
+                ui.ctx().request_repaint_after(Duration::from_secs(1));
+            }
+
+            ui.weak(format!("{:.1} msg/s", self.rate()))
+                .on_hover_text("Messages received per second, over the last 10 seconds.");
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use pubsubman_backend::{
+    message::FrontendMessage,
+    model::{SubscriptionFilter, TopicName},
+};
+use tokio::sync::mpsc::Sender;
+
+use crate::{actions::create_subscription, ui::validity_frame::ValidityFrame};
+
+#[derive(Default)]
+pub struct SubscriptionFilterMenu {
+    text: String,
+    parsed: Option<Result<SubscriptionFilter, String>>,
+    /// The filter that a Subscription has been requested for, but not yet created.
+    pending: Option<Option<SubscriptionFilter>>,
+}
+
+impl SubscriptionFilterMenu {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        front_tx: &Sender<FrontendMessage>,
+        selected_topic: &TopicName,
+        current_filter: Option<&SubscriptionFilter>,
+    ) {
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|pending| pending.as_ref() == current_filter)
+        {
+            self.pending = None;
+        }
+
+        let button_text = if current_filter.is_some() {
+            "Subscription Filter ● ⏷"
+        } else {
+            "Subscription Filter ⏷"
+        };
+
+        ui.menu_button(button_text, |ui| {
+            ui.label(
+                "Only deliver messages with matching attributes to this Topic's Subscription.",
+            );
+            ui.label("Applying a filter creates a new Subscription, and deletes the previous one.");
+
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Current:");
+                match current_filter {
+                    Some(filter) => ui.monospace(filter.as_str()),
+                    None => ui.weak("None"),
+                };
+            });
+
+            ui.add_space(4.0);
+
+            let is_valid = !matches!(self.parsed, Some(Err(_)));
+
+            let response = ui
+                .validity_frame(is_valid)
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.text)
+                            .desired_width(300.0)
+                            .code_editor()
+                            .hint_text(r#"attributes.type = "order""#),
+                    )
+                })
+                .inner;
+
+            if response.changed() {
+                let text = self.text.trim();
+                self.parsed = (!text.is_empty()).then(|| SubscriptionFilter::parse(text));
+            }
+
+            if let Some(Err(err)) = &self.parsed {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+
+            ui.horizontal(|ui| {
+                if self.pending.is_some() {
+                    ui.add_enabled(false, egui::Button::new("Apply"));
+                    ui.spinner();
+                    return;
+                }
+
+                let filter = self
+                    .parsed
+                    .as_ref()
+                    .and_then(|parsed| parsed.as_ref().ok())
+                    .filter(|filter| Some(*filter) != current_filter);
+
+                if ui
+                    .add_enabled(filter.is_some(), egui::Button::new("Apply"))
+                    .clicked()
+                {
+                    self.request_subscription(front_tx, selected_topic, filter.cloned());
+                }
+
+                if ui
+                    .add_enabled(current_filter.is_some(), egui::Button::new("Remove"))
+                    .clicked()
+                {
+                    self.request_subscription(front_tx, selected_topic, None);
+                }
+            });
+        });
+    }
+
+    fn request_subscription(
+        &mut self,
+        front_tx: &Sender<FrontendMessage>,
+        selected_topic: &TopicName,
+        filter: Option<SubscriptionFilter>,
+    ) {
+        create_subscription(front_tx, selected_topic, filter.clone());
+        self.pending = Some(filter);
+    }
+}
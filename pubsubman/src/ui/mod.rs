@@ -1,12 +1,26 @@
+mod details_view;
 mod json_ui;
+mod message_diff;
 mod messages_view;
 mod publish_view;
+mod schema_menu;
 mod selected_message;
+mod stale_subscriptions_view;
+mod topic_list;
 mod topic_name;
+mod topic_tabs;
+mod trace_view;
 mod validity_frame;
 
-pub use json_ui::show_json_context_menu;
-pub use messages_view::MessagesView;
-pub use publish_view::PublishView;
+pub use details_view::DetailsView;
+pub use json_ui::{TraceRequest, show_annotated_json, show_attributes_json};
+pub use message_diff::MessageDiff;
+pub use messages_view::{Expression, MessagesView};
+pub use publish_view::{PublishView, SentMessage, push_sent_message};
+pub use schema_menu::{SchemaChange, SchemaMenu};
 pub use selected_message::render_selected_message;
-pub use topic_name::render_topic_name;
+pub use stale_subscriptions_view::StaleSubscriptionsView;
+pub use topic_list::TopicList;
+pub use topic_name::TopicStatus;
+pub use topic_tabs::{TabAction, render_topic_tabs};
+pub use trace_view::TraceView;
@@ -59,6 +59,12 @@ impl AttributesForm {
     }
 }
 
+impl From<Vec<(String, String)>> for AttributesForm {
+    fn from(value: Vec<(String, String)>) -> Self {
+        Self(value)
+    }
+}
+
 impl From<&AttributesForm> for HashMap<String, String> {
     fn from(value: &AttributesForm) -> Self {
         HashMap::from_iter(value.0.clone())
@@ -0,0 +1,230 @@
+use egui::text::{LayoutJob, TextFormat};
+use egui_json_tree::JsonTreeVisuals;
+use serde_json::Value;
+
+use crate::{topic_schema::TopicSchema, ui::validity_frame::ValidityFrame};
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataMode {
+    /// The data must be valid JSON in order to be published.
+    Json,
+    /// The data is published as-is.
+    #[default]
+    Raw,
+}
+
+#[derive(Default, Hash)]
+pub struct DataForm {
+    text: String,
+    mode: DataMode,
+}
+
+impl DataForm {
+    fn validator(&self, schema: Option<&TopicSchema>) -> DataFormValidator {
+        self.validator_for(&self.text, schema)
+    }
+
+    /// Validates other data in the same way as the form's, such as that of a sent message.
+    pub fn validator_for(&self, text: &str, schema: Option<&TopicSchema>) -> DataFormValidator {
+        let errors = match self.mode {
+            DataMode::Json => match serde_json::from_str::<Value>(text) {
+                Ok(value) => schema
+                    .map(|schema| {
+                        schema
+                            .validate(&value)
+                            .iter()
+                            .map(|violation| format!("Schema violation at {}", violation))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                Err(err) => vec![err.to_string()],
+            },
+            DataMode::Raw => vec![],
+        };
+
+        DataFormValidator(errors)
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, validator: &DataFormValidator) {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.mode, DataMode::Json, "JSON")
+                .on_hover_text("Data must be valid JSON in order to publish.");
+            ui.selectable_value(&mut self.mode, DataMode::Raw, "Raw")
+                .on_hover_text("Publish data as plain text, without validation.");
+
+            ui.separator();
+
+            let can_reformat = self.mode == DataMode::Json && validator.is_valid();
+
+            ui.add_enabled_ui(can_reformat, |ui| {
+                if ui.button("Format").clicked() {
+                    self.reformat(serde_json::to_string_pretty);
+                }
+
+                if ui.button("Minify").clicked() {
+                    self.reformat(serde_json::to_string);
+                }
+            });
+        });
+
+        let mut layouter = |ui: &egui::Ui, text: &dyn egui::TextBuffer, wrap_width: f32| {
+            let mut job = highlight_json(ui, text.as_str());
+            job.wrap.max_width = wrap_width;
+            ui.fonts(|f| f.layout_job(job))
+        };
+
+        let mut text_edit = egui::TextEdit::multiline(&mut self.text)
+            .code_editor()
+            .desired_rows(4)
+            .desired_width(250.0);
+
+        if self.mode == DataMode::Json {
+            text_edit = text_edit.layouter(&mut layouter);
+        }
+
+        ui.validity_frame(validator.is_valid()).show(ui, |ui| {
+            ui.add(text_edit);
+        });
+
+        for err in validator.errors() {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+        }
+    }
+
+    fn reformat(&mut self, to_string: impl Fn(&Value) -> serde_json::Result<String>) {
+        if let Some(formatted) = serde_json::from_str::<Value>(&self.text)
+            .ok()
+            .and_then(|value| to_string(&value).ok())
+        {
+            self.text = formatted;
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct DataFormValidator(Vec<String>);
+
+impl DataFormValidator {
+    pub fn is_valid(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn errors(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl egui::util::cache::ComputerMut<(&DataForm, Option<&TopicSchema>), DataFormValidator>
+    for DataFormValidator
+{
+    fn compute(&mut self, (data, schema): (&DataForm, Option<&TopicSchema>)) -> DataFormValidator {
+        data.validator(schema)
+    }
+}
+
+pub fn data_validator(
+    ctx: &egui::Context,
+    data: &DataForm,
+    schema: Option<&TopicSchema>,
+) -> DataFormValidator {
+    type DataFormValidatorCache =
+        egui::util::cache::FrameCache<DataFormValidator, DataFormValidator>;
+
+    ctx.memory_mut(|mem| {
+        mem.caches
+            .cache::<DataFormValidatorCache>()
+            .get((data, schema))
+    })
+}
+
+/// Lays out JSON text with the same colours that [`egui_json_tree::JsonTree`] uses.
+/// Invalid JSON is still highlighted on a best-effort basis, token by token.
+fn highlight_json(ui: &egui::Ui, text: &str) -> LayoutJob {
+    let visuals = if ui.visuals().dark_mode {
+        JsonTreeVisuals::DARK
+    } else {
+        JsonTreeVisuals::LIGHT
+    };
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let default_color = ui.visuals().text_color();
+
+    let mut job = LayoutJob::default();
+    let mut append = |token: &str, color: egui::Color32| {
+        job.append(token, 0.0, TextFormat::simple(font_id.clone(), color));
+    };
+
+    let bytes = text.as_bytes();
+    let mut start = 0;
+
+    while start < bytes.len() {
+        let end = match bytes[start] {
+            b'"' => {
+                let mut end = start + 1;
+                while end < bytes.len() && bytes[end] != b'"' {
+                    end += if bytes[end] == b'\\' { 2 } else { 1 };
+                }
+                let end = (end + 1).min(bytes.len());
+
+                let is_object_key = text[end..].trim_start().starts_with(':');
+                let color = if is_object_key {
+                    visuals.object_key_color
+                } else {
+                    visuals.string_color
+                };
+                append(&text[start..end], color);
+                end
+            }
+            b'-' | b'0'..=b'9' => {
+                let end = token_end(bytes, start, |b| {
+                    b.is_ascii_digit() || matches!(b, b'-' | b'+' | b'.' | b'e' | b'E')
+                });
+                append(&text[start..end], visuals.number_color);
+                end
+            }
+            b'a'..=b'z' | b'A'..=b'Z' => {
+                let end = token_end(bytes, start, |b| b.is_ascii_alphanumeric());
+                let color = match &text[start..end] {
+                    "true" | "false" => visuals.bool_color,
+                    "null" => visuals.null_color,
+                    _ => default_color,
+                };
+                append(&text[start..end], color);
+                end
+            }
+            b'{' | b'}' | b'[' | b']' | b',' | b':' => {
+                append(&text[start..start + 1], visuals.punctuation_color);
+                start + 1
+            }
+            _ => {
+                // Consume up to the next character that may begin a token, so that
+                // multi-byte characters are never split.
+                let end = text[start..]
+                    .char_indices()
+                    .skip(1)
+                    .find(|(_, c)| c.is_ascii_punctuation() || c.is_ascii_alphanumeric())
+                    .map_or(bytes.len(), |(idx, _)| start + idx);
+                append(&text[start..end], default_color);
+                end
+            }
+        };
+
+        start = end;
+    }
+
+    job
+}
+
+fn token_end(bytes: &[u8], start: usize, is_token_byte: impl Fn(u8) -> bool) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|b| !is_token_byte(*b))
+        .map_or(bytes.len(), |len| start + len)
+}
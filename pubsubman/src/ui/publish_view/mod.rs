@@ -4,15 +4,23 @@ use pubsubman_backend::{
 };
 use tokio::sync::mpsc::Sender;
 
-use crate::actions::publish_message;
+use crate::{actions::publish_message, topic_schema::TopicSchema};
 
-use self::attributes_form::{AttributesForm, attributes_validator};
+use self::{
+    attributes_form::{AttributesForm, attributes_validator},
+    data_form::{DataForm, data_validator},
+    sent_message::{SentMessageAction, render_sent_messages},
+};
+
+pub use self::sent_message::{SentMessage, push_sent_message};
 
 mod attributes_form;
+mod data_form;
+mod sent_message;
 
 #[derive(Default)]
 pub struct PublishView {
-    data: String,
+    data: DataForm,
     attributes: AttributesForm,
 }
 
@@ -22,19 +30,23 @@ impl PublishView {
         ui: &mut egui::Ui,
         front_tx: &Sender<FrontendMessage>,
         selected_topic: &TopicName,
+        sent_messages: &[SentMessage],
+        schema: Option<&TopicSchema>,
     ) {
         ui.heading("Publish New Message");
 
-        egui::CollapsingHeader::new("Data")
+        let mut data_header_text = egui::RichText::new("Data");
+        let data_validator = data_validator(ui.ctx(), &self.data, schema);
+
+        if !data_validator.is_valid() {
+            data_header_text = data_header_text.color(ui.visuals().error_fg_color);
+        }
+
+        egui::CollapsingHeader::new(data_header_text)
             .id_salt(format!("{}-data", selected_topic.0))
             .default_open(true)
             .show(ui, |ui| {
-                ui.add(
-                    egui::TextEdit::multiline(&mut self.data)
-                        .code_editor()
-                        .desired_rows(4)
-                        .desired_width(250.0),
-                );
+                self.data.show(ui, &data_validator);
             });
 
         let mut header_text = egui::RichText::new("Attributes");
@@ -69,19 +81,49 @@ impl PublishView {
                 }
             });
 
+        egui::CollapsingHeader::new(format!("History ({})", sent_messages.len()))
+            .id_salt(format!("{}-history", selected_topic.0))
+            .default_open(false)
+            .show(ui, |ui| match render_sent_messages(ui, sent_messages) {
+                Some(SentMessageAction::Open(idx)) => self.open(&sent_messages[idx]),
+                Some(SentMessageAction::Republish(idx)) => {
+                    let sent_message = &sent_messages[idx];
+
+                    // Held to the same checks as the form, or opened in it to show why not.
+                    if self
+                        .data
+                        .validator_for(&sent_message.data, schema)
+                        .is_valid()
+                    {
+                        publish_message(front_tx, selected_topic, sent_message.into());
+                    } else {
+                        self.open(sent_message);
+                    }
+                }
+                None => {}
+            });
+
         ui.add_space(8.0);
 
         if ui
-            .add_enabled(all_attributes_valid, egui::Button::new("Publish"))
+            .add_enabled(
+                all_attributes_valid && data_validator.is_valid(),
+                egui::Button::new("Publish"),
+            )
             .clicked()
         {
-            publish_message(front_tx, selected_topic, self.into())
+            publish_message(front_tx, selected_topic, self.into());
         }
     }
+
+    fn open(&mut self, sent_message: &SentMessage) {
+        self.data.set_text(sent_message.data.clone());
+        self.attributes = sent_message.attributes.clone().into();
+    }
 }
 
 impl From<&mut PublishView> for PubsubMessageToPublish {
     fn from(val: &mut PublishView) -> Self {
-        Self::new(val.data.clone(), (&val.attributes).into())
+        Self::new(val.data.text().to_string(), (&val.attributes).into())
     }
 }
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Local, Utc};
+use pubsubman_backend::model::PubsubMessageToPublish;
+
+/// The maximum number of sent messages kept per Topic.
+const MAX_SENT_MESSAGES: usize = 50;
+
+/// A message that was published from this app, recorded once the publish is confirmed.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct SentMessage {
+    pub sent_at: DateTime<Utc>,
+    pub data: String,
+    pub attributes: Vec<(String, String)>,
+    /// The ID the server gave the message, which messages sent before it was recorded lack.
+    #[serde(default)]
+    pub message_id: Option<String>,
+}
+
+impl SentMessage {
+    pub fn published(message: &PubsubMessageToPublish, message_id: String) -> Self {
+        let mut attributes: Vec<(String, String)> = message
+            .attributes()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        attributes.sort();
+
+        Self {
+            sent_at: Utc::now(),
+            data: message.data().to_string(),
+            attributes,
+            message_id: Some(message_id),
+        }
+    }
+}
+
+impl From<&SentMessage> for PubsubMessageToPublish {
+    fn from(val: &SentMessage) -> Self {
+        Self::new(val.data.clone(), val.attributes.iter().cloned().collect())
+    }
+}
+
+pub fn push_sent_message(sent_messages: &mut Vec<SentMessage>, sent_message: SentMessage) {
+    sent_messages.push(sent_message);
+
+    if sent_messages.len() > MAX_SENT_MESSAGES {
+        let overflow = sent_messages.len() - MAX_SENT_MESSAGES;
+        sent_messages.drain(..overflow);
+    }
+}
+
+pub enum SentMessageAction {
+    Open(usize),
+    Republish(usize),
+}
+
+pub fn render_sent_messages(
+    ui: &mut egui::Ui,
+    sent_messages: &[SentMessage],
+) -> Option<SentMessageAction> {
+    if sent_messages.is_empty() {
+        ui.label("No messages have been published to this Topic.");
+        return None;
+    }
+
+    let mut action = None;
+
+    egui::ScrollArea::vertical()
+        .max_height(150.0)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            for (idx, sent_message) in sent_messages.iter().enumerate().rev() {
+                ui.horizontal(|ui| {
+                    let local_sent_at: DateTime<Local> = sent_message.sent_at.into();
+                    ui.monospace(format!("{}", local_sent_at.format("%d/%m/%Y %H:%M:%S")))
+                        .on_hover_text(match &sent_message.message_id {
+                            Some(message_id) => format!("Message ID: {}", message_id),
+                            None => "Sent before message IDs were recorded.".to_string(),
+                        });
+
+                    if ui.button("Open").clicked() {
+                        action = Some(SentMessageAction::Open(idx));
+                    }
+
+                    if ui
+                        .button("Republish")
+                        .on_hover_text(
+                            "Publish this message again, if its data passes the checks of the form above. Otherwise it is opened in the form.",
+                        )
+                        .clicked()
+                    {
+                        action = Some(SentMessageAction::Republish(idx));
+                    }
+
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(&sent_message.data).monospace())
+                            .truncate(),
+                    );
+                });
+            }
+        });
+
+    action
+}
@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+
+use crate::topic_schema::TopicSchema;
+
+pub enum SchemaChange {
+    Attached(Box<TopicSchema>),
+    Detached,
+}
+
+#[derive(Default)]
+pub struct SchemaMenu {
+    path: String,
+    error: Option<String>,
+}
+
+impl SchemaMenu {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        schema: Option<&TopicSchema>,
+    ) -> Option<SchemaChange> {
+        let mut change = None;
+
+        let button_text = if schema.is_some() {
+            "Schema ✔ ⏷"
+        } else {
+            "Schema ⏷"
+        };
+
+        ui.menu_button(button_text, |ui| {
+            if let Some(schema) = schema {
+                ui.horizontal(|ui| {
+                    ui.label("Attached:");
+                    ui.monospace(schema.path().display().to_string());
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("Reload").clicked() {
+                        change = self.load(schema.path().to_path_buf());
+                    }
+
+                    if ui.button("Detach").clicked() {
+                        self.error = None;
+                        change = Some(SchemaChange::Detached);
+                    }
+                });
+
+                ui.separator();
+            }
+
+            ui.label("Attach a JSON Schema file to validate message data against.");
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.path)
+                        .desired_width(250.0)
+                        .hint_text("Path to JSON Schema file"),
+                );
+
+                let can_attach = !self.path.trim().is_empty();
+
+                if ui
+                    .add_enabled(can_attach, egui::Button::new("Attach"))
+                    .clicked()
+                {
+                    change = self.load(PathBuf::from(self.path.trim()));
+                }
+            });
+
+            if let Some(err) = &self.error {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+        });
+
+        change
+    }
+
+    fn load(&mut self, path: PathBuf) -> Option<SchemaChange> {
+        match TopicSchema::load(path) {
+            Ok(schema) => {
+                self.error = None;
+                Some(SchemaChange::Attached(Box::new(schema)))
+            }
+            Err(err) => {
+                self.error = Some(err);
+                None
+            }
+        }
+    }
+}
@@ -4,20 +4,36 @@ use pubsubman_backend::{
     message::FrontendMessage,
     model::{PubsubMessage, PubsubMessageToPublish, TopicName},
 };
+use regex::Regex;
 use tokio::sync::mpsc::Sender;
 
-use crate::actions::publish_message;
+use crate::{
+    actions::publish_message,
+    column_settings::ColumnSettings,
+    topic_schema::{TopicSchema, message_schema_violations},
+};
 
-use super::show_json_context_menu;
+use super::{TraceRequest, show_annotated_json, show_attributes_json};
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_selected_message(
     ctx: &egui::Context,
     ui: &mut egui::Ui,
     front_tx: &Sender<FrontendMessage>,
     message: &PubsubMessage,
     selected_topic: &TopicName,
+    schema: Option<&TopicSchema>,
+    search_regex: Option<&Regex>,
+    column_settings: &mut ColumnSettings,
     mut on_close: impl FnMut(),
+    mut on_trace: impl FnMut(TraceRequest),
 ) {
+    let mut trace_request = None;
+
+    let violations = schema
+        .map(|schema| message_schema_violations(ctx, schema, message))
+        .unwrap_or_default();
+
     egui::TopBottomPanel::top("selected_message_top_panel")
         .frame(egui::Frame::side_top_panel(&ctx.style()).inner_margin(8.0))
         .show_inside(ui, |ui| {
@@ -55,6 +71,20 @@ pub fn render_selected_message(
                 ui.monospace(publish_time);
             });
 
+            if !violations.is_empty() {
+                egui::CollapsingHeader::new(
+                    egui::RichText::new(format!("Schema Violations ({})", violations.len()))
+                        .color(ui.visuals().warn_fg_color),
+                )
+                .id_salt("selected_message_violations_collapsing_header")
+                .default_open(true)
+                .show(ui, |ui| {
+                    for violation in violations.iter() {
+                        ui.monospace(violation.to_string());
+                    }
+                });
+            }
+
             egui::CollapsingHeader::new("Data")
                 .id_salt("selected_message_data_collapsing_header")
                 .default_open(false)
@@ -64,7 +94,16 @@ pub fn render_selected_message(
                         &message.data_json,
                     )
                     .default_expand(DefaultExpand::All)
-                    .on_render(show_json_context_menu)
+                    .on_render(|ui, context| {
+                        show_annotated_json(
+                            ui,
+                            context,
+                            &violations,
+                            search_regex,
+                            column_settings,
+                            &mut trace_request,
+                        )
+                    })
                     .show(ui);
                 });
 
@@ -80,11 +119,17 @@ pub fn render_selected_message(
                             &message.attributes_json,
                         )
                         .default_expand(egui_json_tree::DefaultExpand::All)
-                        .on_render(show_json_context_menu)
+                        .on_render(|ui, context| {
+                            show_attributes_json(ui, context, &mut trace_request)
+                        })
                         .show(ui);
                     }
                 });
             ui.allocate_space(ui.available_size());
         });
     });
+
+    if let Some(trace_request) = trace_request {
+        on_trace(trace_request);
+    }
 }
@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use pubsubman_backend::{
+    message::FrontendMessage,
+    model::{CreatedSubscription, SubscriptionName},
+};
+use tokio::sync::mpsc::Sender;
+
+use crate::actions::{delete_subscriptions, list_created_subscriptions};
+
+/// The Subscriptions created by this app that it is no longer using, such as those left behind
+/// after skipping deleting them when quitting, or after crashing, which can be deleted.
+#[derive(Default)]
+pub struct StaleSubscriptionsView {
+    pub is_open: bool,
+    is_loading: bool,
+    stale: Vec<CreatedSubscription>,
+    selected: HashSet<SubscriptionName>,
+    deleting: HashSet<SubscriptionName>,
+    error: Option<String>,
+}
+
+impl StaleSubscriptionsView {
+    pub fn open(&mut self, front_tx: &Sender<FrontendMessage>) {
+        self.is_open = true;
+        self.refresh(front_tx);
+    }
+
+    fn refresh(&mut self, front_tx: &Sender<FrontendMessage>) {
+        self.is_loading = true;
+        self.error = None;
+        list_created_subscriptions(front_tx);
+    }
+
+    /// Keeps the listed Subscriptions that are not in use by this app, selecting them all.
+    pub fn on_listed(
+        &mut self,
+        created: Vec<CreatedSubscription>,
+        is_in_use: impl Fn(&SubscriptionName) -> bool,
+    ) {
+        self.is_loading = false;
+        self.stale = created
+            .into_iter()
+            .filter(|subscription| !is_in_use(&subscription.name))
+            .collect();
+        self.stale
+            .sort_by_key(|subscription| subscription.created_at);
+        self.selected = self
+            .stale
+            .iter()
+            .map(|subscription| subscription.name.clone())
+            .collect();
+    }
+
+    pub fn on_list_failed(&mut self) {
+        self.is_loading = false;
+    }
+
+    pub fn on_subscriptions_deleted(
+        &mut self,
+        results: &[Result<SubscriptionName, SubscriptionName>],
+    ) {
+        let mut failed_count = 0;
+
+        for result in results {
+            match result {
+                Ok(sub_name) if self.deleting.remove(sub_name) => {
+                    self.stale
+                        .retain(|subscription| subscription.name != *sub_name);
+                    self.selected.remove(sub_name);
+                }
+                Err(sub_name) if self.deleting.remove(sub_name) => failed_count += 1,
+                _ => {}
+            }
+        }
+
+        if failed_count > 0 {
+            self.error = Some(format!(
+                "Failed to delete {} Subscription{}.",
+                failed_count,
+                if failed_count == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, front_tx: &Sender<FrontendMessage>) {
+        let mut is_open = self.is_open;
+
+        egui::Window::new("Clean Up Stale Subscriptions")
+            .open(&mut is_open)
+            .default_size([700.0, 400.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(
+                    "Subscriptions created by pubsubman across the project, that this app is not using.",
+                );
+                ui.weak(
+                    "They may still be in use by pubsubman on another host. Unused Subscriptions expire after a day of inactivity.",
+                );
+
+                ui.add_space(4.0);
+
+                let is_busy = self.is_loading || !self.deleting.is_empty();
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!is_busy, egui::Button::new("⟳ Refresh"))
+                        .clicked()
+                    {
+                        self.refresh(front_tx);
+                    }
+
+                    if ui
+                        .add_enabled(!is_busy, egui::Button::new("Select All"))
+                        .clicked()
+                    {
+                        self.selected = self
+                            .stale
+                            .iter()
+                            .map(|subscription| subscription.name.clone())
+                            .collect();
+                    }
+
+                    if ui
+                        .add_enabled(!is_busy, egui::Button::new("Select None"))
+                        .clicked()
+                    {
+                        self.selected.clear();
+                    }
+
+                    let delete_button = egui::Button::new(format!(
+                        "Delete Selected ({})",
+                        self.selected.len()
+                    ));
+
+                    if ui
+                        .add_enabled(!is_busy && !self.selected.is_empty(), delete_button)
+                        .clicked()
+                    {
+                        self.error = None;
+                        self.deleting = self.selected.clone();
+                        delete_subscriptions(front_tx, self.deleting.iter().cloned().collect());
+                    }
+
+                    if is_busy {
+                        ui.spinner();
+                    }
+                });
+
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+
+                ui.separator();
+
+                if self.stale.is_empty() {
+                    if !self.is_loading {
+                        ui.label("No stale Subscriptions were found.");
+                    }
+                    return;
+                }
+
+                self.show_subscriptions(ui);
+            });
+
+        self.is_open = is_open;
+    }
+
+    fn show_subscriptions(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                egui::Grid::new("stale_subscriptions_grid")
+                    .num_columns(5)
+                    .striped(true)
+                    .spacing((12.0, 4.0))
+                    .show(ui, |ui| {
+                        ui.label("");
+                        for title in ["Subscription", "Topic", "Host", "Created"] {
+                            ui.strong(title);
+                        }
+                        ui.end_row();
+
+                        for subscription in &self.stale {
+                            let mut is_selected = self.selected.contains(&subscription.name);
+
+                            if ui.checkbox(&mut is_selected, "").changed() {
+                                if is_selected {
+                                    self.selected.insert(subscription.name.clone());
+                                } else {
+                                    self.selected.remove(&subscription.name);
+                                }
+                            }
+
+                            let sub_id = subscription
+                                .name
+                                .0
+                                .rsplit_once("/subscriptions/")
+                                .map_or(subscription.name.0.as_str(), |(_, id)| id);
+
+                            ui.monospace(sub_id).on_hover_text(&subscription.name.0);
+                            ui.label(subscription.topic_name.short_id())
+                                .on_hover_text(&subscription.topic_name.0);
+
+                            match &subscription.host {
+                                Some(host) => ui.label(host),
+                                None => ui.weak("Unknown"),
+                            };
+
+                            match subscription.created_at {
+                                Some(created_at) => {
+                                    let age = Utc::now() - created_at;
+                                    ui.label(format_age(age))
+                                        .on_hover_text(created_at.to_rfc3339())
+                                }
+                                None => ui.weak("Unknown"),
+                            };
+
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+fn format_age(age: chrono::TimeDelta) -> String {
+    if age.num_days() > 0 {
+        format!("{}d ago", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h ago", age.num_hours())
+    } else {
+        format!("{}m ago", age.num_minutes())
+    }
+}
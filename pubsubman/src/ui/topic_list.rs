@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use pubsubman_backend::model::TopicName;
+
+use super::topic_name::{TopicStatus, render_topic_name};
+
+/// The characters that separate the segments of a Topic ID, such as `orders.created`.
+const SEGMENT_SEPARATORS: [char; 3] = ['.', '-', '_'];
+
+/// The Topics listed in the side panel, which can be filtered, pinned as favourites and
+/// grouped by the prefix of their IDs.
+#[derive(Default)]
+pub struct TopicList {
+    filter: String,
+}
+
+enum TopicListEntry<'a> {
+    Group(&'a str, Vec<&'a TopicName>),
+    Topic(&'a TopicName),
+}
+
+impl TopicList {
+    /// Returns the Topic that was clicked, if any.
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        topic_names: &[TopicName],
+        favourites: &mut Vec<TopicName>,
+        group_by_prefix: bool,
+        status: impl Fn(&TopicName) -> TopicStatus,
+    ) -> Option<TopicName> {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.filter)
+                    .desired_width(ui.available_width() - 30.0)
+                    .hint_text("Filter Topics"),
+            );
+
+            if ui
+                .add_enabled(!self.filter.is_empty(), egui::Button::new("✖"))
+                .on_hover_text("Clear filter.")
+                .clicked()
+            {
+                self.filter.clear();
+            }
+        });
+
+        let filter = self.filter.to_lowercase();
+        let is_filtering = !filter.is_empty();
+        let matching: Vec<&TopicName> = topic_names
+            .iter()
+            .filter(|topic_name| topic_name.short_id().to_lowercase().contains(&filter))
+            .collect();
+
+        if matching.is_empty() && is_filtering {
+            ui.weak("No Topics match the filter.");
+        }
+
+        let mut clicked_topic = None;
+        let mut toggled_favourite = None;
+
+        let mut render_topic = |ui: &mut egui::Ui, topic_name: &TopicName| {
+            let status = TopicStatus {
+                is_favourite: favourites.contains(topic_name),
+                ..status(topic_name)
+            };
+
+            render_topic_name(
+                ui,
+                topic_name,
+                &status,
+                || clicked_topic = Some(topic_name.clone()),
+                || toggled_favourite = Some(topic_name.clone()),
+            );
+        };
+
+        // Favourites are listed in the order they were pinned, and only there.
+        let matching_favourites: Vec<&TopicName> = favourites
+            .iter()
+            .filter(|favourite| matching.contains(favourite))
+            .collect();
+
+        if !matching_favourites.is_empty() {
+            egui::CollapsingHeader::new(format!("★ Favourites ({})", matching_favourites.len()))
+                .id_salt("favourite_topics")
+                .default_open(true)
+                .show(ui, |ui| {
+                    for topic_name in matching_favourites.iter().copied() {
+                        render_topic(ui, topic_name);
+                    }
+                });
+        }
+
+        let others: Vec<&TopicName> = matching
+            .into_iter()
+            .filter(|topic_name| !matching_favourites.contains(topic_name))
+            .collect();
+
+        let entries = if group_by_prefix {
+            group_by_id_prefix(others)
+        } else {
+            others.into_iter().map(TopicListEntry::Topic).collect()
+        };
+
+        for entry in entries {
+            match entry {
+                TopicListEntry::Group(prefix, topic_names) => {
+                    let unread_count: usize = topic_names
+                        .iter()
+                        .map(|topic_name| status(topic_name).unread_count)
+                        .sum();
+
+                    let mut header_text = format!("{}* ({})", prefix, topic_names.len());
+
+                    if unread_count > 0 {
+                        header_text.push_str(&format!(" · {} unread", unread_count));
+                    }
+
+                    egui::CollapsingHeader::new(header_text)
+                        .id_salt(("topic_group", prefix))
+                        .default_open(false)
+                        // Show every match while filtering.
+                        .open(is_filtering.then_some(true))
+                        .show(ui, |ui| {
+                            for topic_name in topic_names {
+                                render_topic(ui, topic_name);
+                            }
+                        });
+                }
+                TopicListEntry::Topic(topic_name) => render_topic(ui, topic_name),
+            }
+        }
+
+        if let Some(topic_name) = toggled_favourite {
+            match favourites
+                .iter()
+                .position(|favourite| *favourite == topic_name)
+            {
+                Some(idx) => {
+                    favourites.remove(idx);
+                }
+                None => favourites.push(topic_name),
+            }
+        }
+
+        clicked_topic
+    }
+}
+
+/// Groups the Topics whose IDs share their first segment, such as `orders.` in
+/// `orders.created` and `orders.shipped`, leaving the others ungrouped, all in order of ID.
+fn group_by_id_prefix(topic_names: Vec<&TopicName>) -> Vec<TopicListEntry<'_>> {
+    let mut groups: BTreeMap<&str, Vec<&TopicName>> = BTreeMap::new();
+
+    for topic_name in topic_names {
+        let short_id = topic_name.short_id();
+        let prefix = short_id
+            .find(SEGMENT_SEPARATORS)
+            .map_or(short_id, |idx| &short_id[..=idx]);
+
+        groups.entry(prefix).or_default().push(topic_name);
+    }
+
+    let mut entries = vec![];
+
+    for (prefix, mut topic_names) in groups {
+        topic_names.sort_by_key(|topic_name| topic_name.short_id());
+
+        if topic_names.len() > 1 {
+            entries.push(TopicListEntry::Group(prefix, topic_names));
+        } else {
+            entries.extend(topic_names.into_iter().map(TopicListEntry::Topic));
+        }
+    }
+
+    entries
+}
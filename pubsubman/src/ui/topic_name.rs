@@ -1,12 +1,26 @@
 use pubsubman_backend::model::TopicName;
 
+/// How a Topic is shown in the topics side panel.
+#[derive(Default)]
+pub struct TopicStatus {
+    pub is_selected: bool,
+    pub is_streaming: bool,
+    pub is_favourite: bool,
+    pub unread_count: usize,
+    /// Whether the Topic was added by a recent refresh.
+    pub is_added: bool,
+    /// Whether the Topic was removed by a recent refresh, in which case it can't be selected.
+    pub is_removed: bool,
+}
+
 pub fn render_topic_name(
     ui: &mut egui::Ui,
     topic_name: &TopicName,
-    is_selected: bool,
+    status: &TopicStatus,
     on_click: impl FnOnce(),
+    on_toggle_favourite: impl FnOnce(),
 ) {
-    let (stroke, fill) = if is_selected {
+    let (stroke, fill) = if status.is_selected {
         let egui::style::Selection { stroke, bg_fill } = ui.visuals().selection;
         (stroke, bg_fill)
     } else {
@@ -22,18 +36,81 @@ pub fn render_topic_name(
         .show(ui, |ui| {
             ui.set_width(ui.available_width());
 
-            let mut text = egui::RichText::new(&topic_name.0);
+            // Lay out the indicators from the right first, so that a long name is truncated
+            // rather than pushing them out of view.
+            ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if status.unread_count > 0 {
+                        egui::Frame::NONE
+                            .fill(ui.visuals().selection.bg_fill)
+                            .inner_margin(egui::vec2(5.0, 0.0))
+                            .corner_radius(8.0)
+                            .show(ui, |ui| {
+                                ui.small(status.unread_count.to_string());
+                            })
+                            .response
+                            .on_hover_text("Messages received since this Topic was last viewed.");
+                    }
+
+                    if status.is_added {
+                        ui.colored_label(egui::Color32::from_rgb(80, 200, 120), "NEW")
+                            .on_hover_text("This Topic was recently created.");
+                    }
+
+                    if status.is_streaming {
+                        ui.colored_label(egui::Color32::from_rgb(80, 200, 120), "●")
+                            .on_hover_text("Streaming messages.");
+                    }
+
+                    ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                        let (favourite_text, favourite_hover_text) = if status.is_favourite {
+                            ("★", "Unpin from Favourites.")
+                        } else {
+                            ("☆", "Pin to Favourites.")
+                        };
+
+                        if ui
+                            .add(
+                                egui::Label::new(egui::RichText::new(favourite_text).weak())
+                                    .sense(egui::Sense::click()),
+                            )
+                            .on_hover_text(favourite_hover_text)
+                            .clicked()
+                        {
+                            on_toggle_favourite();
+                        }
+
+                        let mut text = egui::RichText::new(topic_name.short_id());
+
+                        if status.is_selected {
+                            text = text.color(stroke.color);
+                        }
+
+                        if status.is_removed {
+                            text = text.strikethrough().weak();
+                        }
 
-            if is_selected {
-                text = text.color(stroke.color);
-            }
+                        let hover_text = if status.is_removed {
+                            format!("{}\n\nThis Topic was recently deleted.", topic_name)
+                        } else {
+                            topic_name.0.clone()
+                        };
 
-            if ui
-                .add(egui::Label::new(text).sense(egui::Sense::click()))
-                .clicked()
-            {
-                on_click()
-            }
+                        if ui
+                            .add(
+                                egui::Label::new(text)
+                                    .truncate()
+                                    .sense(egui::Sense::click()),
+                            )
+                            .on_hover_text(hover_text)
+                            .clicked()
+                            && !status.is_removed
+                        {
+                            on_click()
+                        }
+                    });
+                });
+            });
         })
         .response
         .on_hover_cursor(egui::CursorIcon::PointingHand);
@@ -0,0 +1,78 @@
+use crate::settings::PaneSettings;
+
+pub enum TabAction {
+    Activate(usize),
+    Close(usize),
+    SplitRight(usize),
+    MergeLeft,
+}
+
+pub fn render_topic_tabs(
+    ui: &mut egui::Ui,
+    pane: &PaneSettings,
+    is_focused: bool,
+    can_merge_left: bool,
+) -> Option<TabAction> {
+    let mut action = None;
+
+    egui::ScrollArea::horizontal()
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                for (tab_idx, topic_name) in pane.tabs.iter().enumerate() {
+                    let is_active = tab_idx == pane.active_tab;
+
+                    let mut text = egui::RichText::new(topic_name.short_id());
+
+                    if is_active && is_focused {
+                        text = text.color(ui.visuals().selection.stroke.color);
+                    }
+
+                    let tab = ui
+                        .selectable_label(is_active, text)
+                        .on_hover_text(format!("{}\n\nRight click for more options.", topic_name));
+
+                    if tab.clicked() {
+                        action = Some(TabAction::Activate(tab_idx));
+                    }
+
+                    if tab.middle_clicked() {
+                        action = Some(TabAction::Close(tab_idx));
+                    }
+
+                    tab.context_menu(|ui| {
+                        if ui
+                            .add_enabled(pane.tabs.len() > 1, egui::Button::new("Split Right"))
+                            .on_hover_text("Move this tab into a new pane, to the right.")
+                            .clicked()
+                        {
+                            action = Some(TabAction::SplitRight(tab_idx));
+                            ui.close();
+                        }
+
+                        if ui
+                            .add_enabled(can_merge_left, egui::Button::new("Merge Left"))
+                            .on_hover_text("Move all tabs in this pane into the pane to the left.")
+                            .clicked()
+                        {
+                            action = Some(TabAction::MergeLeft);
+                            ui.close();
+                        }
+
+                        if ui.button("Close").clicked() {
+                            action = Some(TabAction::Close(tab_idx));
+                            ui.close();
+                        }
+                    });
+
+                    if ui.small_button("✖").on_hover_text("Close").clicked() {
+                        action = Some(TabAction::Close(tab_idx));
+                    }
+
+                    ui.separator();
+                }
+            });
+        });
+
+    action
+}
@@ -0,0 +1,413 @@
+use std::hash::{Hash, Hasher};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use egui_extras::{Column, TableBuilder};
+use pubsubman_backend::model::{PubsubMessage, TopicName};
+
+use crate::{column_settings::ColumnKind, message_store::MessageStore};
+
+use super::messages_view::data_preview;
+
+/// The maximum number of messages shown in a trace.
+const TRACE_LIMIT: usize = 1000;
+
+/// A timeline of the messages received across all Topics that share a correlation value,
+/// such as a `trace_id` attribute, to follow a request through a pipeline.
+#[derive(Default)]
+pub struct TraceView {
+    pub is_open: bool,
+    key_kind: CorrelationKeyKind,
+    key: String,
+    value: String,
+    loaded: Option<LoadedTrace>,
+    error: Option<String>,
+    /// The trace to read on the next frame. Reading it scans every stored message, so it is
+    /// only read on request, rather than as the key or value are typed.
+    requested: Option<(ColumnKind, String)>,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+enum CorrelationKeyKind {
+    #[default]
+    Attribute,
+    JsonPointer,
+}
+
+struct LoadedTrace {
+    column: ColumnKind,
+    value: String,
+    generation: u64,
+    messages: Vec<(TopicName, PubsubMessage)>,
+}
+
+impl TraceView {
+    /// Opens the trace of messages whose attribute or JSON pointer has the given value.
+    pub fn trace(&mut self, column: ColumnKind, value: String) {
+        match column {
+            ColumnKind::Attribute(key) => {
+                self.key_kind = CorrelationKeyKind::Attribute;
+                self.key = key;
+            }
+            ColumnKind::JsonPointer(pointer) => {
+                self.key_kind = CorrelationKeyKind::JsonPointer;
+                self.key = pointer;
+            }
+            _ => return,
+        }
+
+        self.value = value;
+        self.is_open = true;
+        self.request_trace();
+    }
+
+    fn request_trace(&mut self) {
+        self.requested = self
+            .column()
+            .filter(|_| !self.value.is_empty())
+            .map(|column| (column, self.value.clone()));
+    }
+
+    /// Returns the message clicked on, along with its Topic.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        message_store: &MessageStore,
+    ) -> Option<(TopicName, PubsubMessage)> {
+        let mut is_open = self.is_open;
+        let mut clicked_message = None;
+
+        egui::Window::new("Trace Messages")
+            .open(&mut is_open)
+            .default_size([800.0, 400.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                self.show_key_inputs(ui);
+                self.load(message_store);
+                self.show_refresh(ui, message_store);
+
+                ui.separator();
+
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+
+                match &self.loaded {
+                    Some(loaded) if !loaded.messages.is_empty() => {
+                        show_summary(ui, &loaded.messages);
+                        ui.add_space(4.0);
+                        clicked_message = render_timeline(ui, &loaded.messages);
+                    }
+                    Some(_) => {
+                        ui.label("No received messages share this value.");
+                    }
+                    None => {
+                        ui.label(
+                            "Enter a key and value, then press Enter to trace messages across all Topics.",
+                        );
+                    }
+                }
+            });
+
+        self.is_open = is_open;
+
+        clicked_message
+    }
+
+    fn show_key_inputs(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("trace_key_kind_combo_box")
+                .selected_text(match self.key_kind {
+                    CorrelationKeyKind::Attribute => "Attribute",
+                    CorrelationKeyKind::JsonPointer => "JSON Pointer",
+                })
+                .width(90.0)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut self.key_kind,
+                        CorrelationKeyKind::Attribute,
+                        "Attribute",
+                    );
+                    ui.selectable_value(
+                        &mut self.key_kind,
+                        CorrelationKeyKind::JsonPointer,
+                        "JSON Pointer",
+                    );
+                });
+
+            let key_response = ui.add(
+                egui::TextEdit::singleline(&mut self.key)
+                    .desired_width(150.0)
+                    .code_editor()
+                    .hint_text(match self.key_kind {
+                        CorrelationKeyKind::Attribute => "trace_id",
+                        CorrelationKeyKind::JsonPointer => "/order/id",
+                    }),
+            );
+
+            ui.label("=");
+
+            let value_response = ui.add(
+                egui::TextEdit::singleline(&mut self.value)
+                    .desired_width(250.0)
+                    .code_editor()
+                    .hint_text("Value"),
+            );
+
+            let is_submitted = (key_response.lost_focus() || value_response.lost_focus())
+                && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let can_trace = self.column().is_some() && !self.value.is_empty();
+
+            if ui
+                .add_enabled(can_trace, egui::Button::new("Trace"))
+                .on_disabled_hover_text(match self.key_kind {
+                    CorrelationKeyKind::Attribute => "Enter an attribute key and value.",
+                    CorrelationKeyKind::JsonPointer => {
+                        "Enter a JSON pointer, starting with '/', and value."
+                    }
+                })
+                .clicked()
+                || (is_submitted && can_trace)
+            {
+                self.request_trace();
+            }
+        });
+    }
+
+    fn column(&self) -> Option<ColumnKind> {
+        match self.key_kind {
+            CorrelationKeyKind::Attribute if !self.key.is_empty() => {
+                Some(ColumnKind::Attribute(self.key.clone()))
+            }
+            CorrelationKeyKind::JsonPointer if self.key.starts_with('/') => {
+                Some(ColumnKind::JsonPointer(self.key.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    fn load(&mut self, message_store: &MessageStore) {
+        let Some((column, value)) = self.requested.take() else {
+            return;
+        };
+
+        let generation = message_store.total_generation();
+
+        match message_store.read_correlated(&column, &value, TRACE_LIMIT) {
+            Ok(messages) => {
+                self.error = None;
+                self.loaded = Some(LoadedTrace {
+                    column,
+                    value,
+                    generation,
+                    messages,
+                });
+            }
+            Err(err) => {
+                self.error = Some(format!("Failed to read the trace: {}", err));
+                self.loaded = None;
+            }
+        }
+    }
+
+    fn show_refresh(&mut self, ui: &mut egui::Ui, message_store: &MessageStore) {
+        let Some(loaded) = &self.loaded else {
+            return;
+        };
+
+        let is_edited =
+            self.column().as_ref() != Some(&loaded.column) || self.value != loaded.value;
+        let mut refresh = false;
+
+        ui.horizontal(|ui| {
+            refresh = ui
+                .button("⟳ Refresh")
+                .on_hover_text("Include the messages received since the trace was loaded.")
+                .clicked();
+
+            if loaded.generation != message_store.total_generation() {
+                ui.weak("Messages have been received since the trace was loaded.");
+            }
+
+            if is_edited {
+                ui.weak(format!(
+                    "Showing the trace of {} = {}.",
+                    loaded.column, loaded.value
+                ));
+            }
+        });
+
+        if refresh {
+            self.requested = Some((loaded.column.clone(), loaded.value.clone()));
+            ui.ctx().request_repaint();
+        }
+    }
+}
+
+fn show_summary(ui: &mut egui::Ui, messages: &[(TopicName, PubsubMessage)]) {
+    let mut topic_names: Vec<&TopicName> = messages.iter().map(|(topic, _)| topic).collect();
+    topic_names.sort();
+    topic_names.dedup();
+
+    let span = time_span(messages)
+        .map(|(first, last)| format!(", spanning {}", format_offset(last - first)))
+        .unwrap_or_default();
+
+    let limit_text = if messages.len() == TRACE_LIMIT {
+        " (showing the earliest only)"
+    } else {
+        ""
+    };
+
+    ui.weak(format!(
+        "{} messages across {} Topics{}{}",
+        messages.len(),
+        topic_names.len(),
+        span,
+        limit_text
+    ));
+}
+
+/// Renders the messages in order of publish time, each with a marker at its time relative to
+/// the first and last. Returns the message whose ID was clicked, if any.
+fn render_timeline(
+    ui: &mut egui::Ui,
+    messages: &[(TopicName, PubsubMessage)],
+) -> Option<(TopicName, PubsubMessage)> {
+    let span = time_span(messages);
+    let row_height = ui
+        .text_style_height(&egui::TextStyle::Body)
+        .max(ui.spacing().interact_size.y);
+    let mut clicked_message = None;
+
+    TableBuilder::new(ui)
+        .id_salt("trace_timeline")
+        .striped(true)
+        .resizable(true)
+        .auto_shrink([false, true])
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::exact(120.0))
+        .column(Column::initial(80.0).at_least(40.0).clip(true))
+        .column(Column::initial(150.0).at_least(40.0).clip(true))
+        .column(Column::initial(150.0).at_least(40.0).clip(true))
+        .column(Column::remainder().at_least(40.0).clip(true))
+        .header(row_height, |mut header| {
+            for title in ["Timeline", "Offset", "Topic", "ID", "Data"] {
+                header.col(|ui| {
+                    ui.strong(title);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(row_height, messages.len(), |mut row| {
+                let (topic_name, message) = &messages[row.index()];
+                let color = topic_color(topic_name);
+
+                row.col(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(
+                        egui::vec2(ui.available_width(), row_height),
+                        egui::Sense::hover(),
+                    );
+                    let track_y = rect.center().y;
+                    let track = egui::Rangef::new(rect.left() + 4.0, rect.right() - 4.0);
+
+                    ui.painter().hline(
+                        track,
+                        track_y,
+                        egui::Stroke::new(1.0, ui.visuals().weak_text_color()),
+                    );
+
+                    if let (Some((first, last)), Some(publish_time)) = (span, message.publish_time)
+                    {
+                        let total = (last - first).num_microseconds().unwrap_or_default();
+                        let offset = (publish_time - first)
+                            .num_microseconds()
+                            .unwrap_or_default();
+                        let t = if total > 0 {
+                            offset as f32 / total as f32
+                        } else {
+                            0.0
+                        };
+
+                        ui.painter().circle_filled(
+                            egui::pos2(egui::lerp(track, t), track_y),
+                            4.0,
+                            color,
+                        );
+                    }
+                });
+
+                row.col(|ui| {
+                    let offset = span
+                        .zip(message.publish_time)
+                        .map(|((first, _), publish_time)| {
+                            format!("+{}", format_offset(publish_time - first))
+                        })
+                        .unwrap_or_default();
+
+                    ui.monospace(offset)
+                        .on_hover_text(ColumnKind::PublishTime.text(message));
+                });
+
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(topic_name.short_id()).color(color))
+                            .truncate(),
+                    )
+                    .on_hover_text(&topic_name.0);
+                });
+
+                row.col(|ui| {
+                    if ui
+                        .link(&message.id)
+                        .on_hover_text("Open this message in its Topic.")
+                        .clicked()
+                    {
+                        clicked_message = Some((topic_name.clone(), message.clone()));
+                    }
+                });
+
+                row.col(|ui| {
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(data_preview(message)).monospace())
+                            .truncate(),
+                    );
+                });
+            });
+        });
+
+    clicked_message
+}
+
+/// The earliest and latest publish times of the messages.
+fn time_span(messages: &[(TopicName, PubsubMessage)]) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut publish_times = messages
+        .iter()
+        .filter_map(|(_, message)| message.publish_time);
+    let first = publish_times.next()?;
+    let (first, last) = publish_times.fold((first, first), |(min, max), publish_time| {
+        (min.min(publish_time), max.max(publish_time))
+    });
+
+    Some((first, last))
+}
+
+fn format_offset(offset: TimeDelta) -> String {
+    let millis = offset.num_milliseconds();
+
+    if millis < 1000 {
+        format!("{}ms", millis)
+    } else if millis < 60_000 {
+        format!("{:.3}s", millis as f64 / 1000.0)
+    } else {
+        format!("{}m {}s", millis / 60_000, (millis % 60_000) / 1000)
+    }
+}
+
+/// A colour for the Topic that stays the same between frames.
+fn topic_color(topic_name: &TopicName) -> egui::Color32 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    topic_name.hash(&mut hasher);
+    let hue = (hasher.finish() % 360) as f32 / 360.0;
+
+    egui::ecolor::Hsva::new(hue, 0.55, 0.85, 1.0).into()
+}
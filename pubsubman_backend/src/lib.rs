@@ -1,28 +1,54 @@
 use std::{error::Error, sync::Arc};
 
-use futures_util::StreamExt;
-use google_cloud_gax::conn::Environment;
+use google_cloud_gax::{
+    conn::{ConnectionOptions, Environment},
+    create_request,
+    grpc::{Code, Status},
+};
+use google_cloud_googleapis::pubsub::v1::{
+    GetTopicRequest, ListSubscriptionsRequest, PushConfig,
+    publisher_client::PublisherClient as TopicAdminClient,
+    subscriber_client::SubscriberClient as SubscriptionAdminClient,
+};
 use google_cloud_pubsub::{
+    apiv1::conn_pool::ConnectionManager,
     client::{Client, ClientConfig},
-    subscription::SubscriptionConfig,
+    subscription::{SubscriptionConfig, SubscriptionConfigToUpdate},
 };
 use message::{BackendError, BackendMessage, FrontendMessage};
-use model::{PubsubMessageToPublish, SubscriptionName, TopicName};
+use model::{
+    CreatedSubscription, PubsubMessageToPublish, SubscriptionFilter, SubscriptionMetadata,
+    SubscriptionName, SubscriptionUpdate, TopicMetadata, TopicName, TopicUpdate,
+    created_subscription_expiration_policy, created_subscription_labels,
+};
+use push_receiver::{PushReceiver, PushRoutes, push_endpoint};
 use tokio::{
     runtime::{Builder, Runtime},
     select,
     sync::mpsc::{Receiver, Sender},
 };
 use tokio_util::sync::CancellationToken;
+use ui_sender::UiSender;
 use uuid::Uuid;
 
 pub mod message;
 pub mod model;
+mod push_receiver;
+mod stream;
+mod ui_sender;
 
 pub struct Backend {
-    back_tx: Sender<BackendMessage>,
+    /// Messages for the UI are dropped once it has closed, rather than failing.
+    back_tx: UiSender,
     front_rx: Receiver<FrontendMessage>,
     client: Arc<Client>,
+    project_id: String,
+    /// The [`Client`] does not expose reading the configuration of Topics, nor clearing the
+    /// settings of Subscriptions, so these go through lower level clients over these
+    /// connections instead.
+    admin_conns: Arc<ConnectionManager>,
+    push_receiver: Option<PushReceiver>,
+    push_routes: PushRoutes,
     // Store and reuse the same runtime (that created the client) for async operations,
     // because the gPRC service appears to require the same runtime that created it:
     // https://github.com/hyperium/tonic/issues/942#issuecomment-1313396286
@@ -30,11 +56,14 @@ pub struct Backend {
 }
 
 impl Backend {
+    /// The UI is repainted through the `egui_ctx` whenever a message is sent to it.
     pub fn new(
         back_tx: Sender<BackendMessage>,
         front_rx: Receiver<FrontendMessage>,
         emulator_project_id: Option<String>,
+        egui_ctx: egui::Context,
     ) -> Result<Self, Box<dyn Error>> {
+        let back_tx = UiSender::new(back_tx, egui_ctx);
         let rt = Builder::new_multi_thread()
             .worker_threads(4)
             .enable_all()
@@ -42,27 +71,30 @@ impl Backend {
             .unwrap();
 
         match rt.block_on(async { create_client(emulator_project_id).await }) {
-            Ok((client, project_id)) => {
+            Ok((client, admin_conns, project_id)) => {
                 let back_tx_clone = back_tx.clone();
+                let project_id_clone = project_id.clone();
                 rt.spawn(async move {
-                    back_tx_clone
-                        .send(BackendMessage::ClientInitialised(project_id))
-                        .await
-                        .unwrap();
+                    let _ = back_tx_clone
+                        .send(BackendMessage::ClientInitialised(project_id_clone))
+                        .await;
                 });
                 Ok(Self {
                     back_tx,
                     front_rx,
                     client: Arc::new(client),
+                    project_id,
+                    admin_conns: Arc::new(admin_conns),
+                    push_receiver: None,
+                    push_routes: PushRoutes::default(),
                     rt,
                 })
             }
             Err(err) => {
                 rt.spawn(async move {
-                    back_tx
+                    let _ = back_tx
                         .send(BackendMessage::Error(BackendError::ClientInitFailed))
-                        .await
-                        .unwrap();
+                        .await;
                 });
                 Err(err)
             }
@@ -75,8 +107,8 @@ impl Backend {
                 FrontendMessage::RefreshTopicsRequest => {
                     self.get_topics();
                 }
-                FrontendMessage::CreateSubscriptionRequest(topic_name) => {
-                    self.create_subscription(topic_name);
+                FrontendMessage::CreateSubscriptionRequest(topic_name, filter) => {
+                    self.create_subscription(topic_name, filter);
                 }
                 FrontendMessage::DeleteSubscriptions(sub_names) => {
                     self.delete_subscriptions(sub_names);
@@ -87,6 +119,33 @@ impl Backend {
                 FrontendMessage::PublishMessage(topic_name, message) => {
                     self.publish_message(topic_name, message);
                 }
+                FrontendMessage::GetTopicMetadata(topic_name) => {
+                    self.get_topic_metadata(topic_name);
+                }
+                FrontendMessage::GetSubscriptionMetadata(topic_name, sub_name) => {
+                    self.get_subscription_metadata(topic_name, sub_name);
+                }
+                FrontendMessage::UpdateTopic(topic_name, update) => {
+                    self.update_topic(topic_name, update);
+                }
+                FrontendMessage::UpdateSubscription(topic_name, sub_name, update) => {
+                    self.update_subscription(topic_name, sub_name, update);
+                }
+                FrontendMessage::CreatePushSubscriptionRequest(topic_name, port) => {
+                    self.create_push_subscription(topic_name, port);
+                }
+                FrontendMessage::ReceivePushMessages(topic_name, sub_name, port) => {
+                    self.receive_push_messages(topic_name, sub_name, port);
+                }
+                FrontendMessage::StopPushMessages(sub_name) => {
+                    self.push_routes.lock().unwrap().remove(&sub_name);
+                }
+                FrontendMessage::ListCreatedSubscriptions => {
+                    self.list_created_subscriptions();
+                }
+                FrontendMessage::ValidateSubscriptions(subscriptions) => {
+                    self.validate_subscriptions(subscriptions);
+                }
             }
         }
     }
@@ -107,27 +166,41 @@ impl Backend {
                     BackendMessage::Error(BackendError::GetTopicsFailed)
                 });
 
-            back_tx.send(message).await.unwrap();
+            let _ = back_tx.send(message).await;
         });
     }
 
-    fn create_subscription(&self, topic_name: TopicName) {
+    fn create_subscription(&self, topic_name: TopicName, filter: Option<SubscriptionFilter>) {
         let back_tx = self.back_tx.clone();
         let client = self.client.clone();
 
         self.rt.spawn(async move {
+            let config = SubscriptionConfig {
+                filter: filter
+                    .as_ref()
+                    .map(|filter| filter.as_str().to_owned())
+                    .unwrap_or_default(),
+                labels: created_subscription_labels(),
+                expiration_policy: Some(created_subscription_expiration_policy()),
+                ..Default::default()
+            };
+
             let message = match client
                 .create_subscription(
                     &format!("pubsubman-subscription-{}", Uuid::new_v4()),
                     &topic_name.0,
-                    SubscriptionConfig::default(),
+                    config,
                     None,
                 )
                 .await
             {
                 Ok(subscription) => {
                     let fq_sub_name = subscription.fully_qualified_name().to_owned();
-                    BackendMessage::SubscriptionCreated(topic_name, SubscriptionName(fq_sub_name))
+                    BackendMessage::SubscriptionCreated(
+                        topic_name,
+                        SubscriptionName(fq_sub_name),
+                        filter,
+                    )
                 }
                 Err(status) => {
                     eprintln!("{}", status);
@@ -135,7 +208,7 @@ impl Backend {
                 }
             };
 
-            back_tx.send(message).await.unwrap();
+            let _ = back_tx.send(message).await;
         });
     }
 
@@ -160,55 +233,235 @@ impl Backend {
 
             let results = futures::future::join_all(futures).await;
 
-            back_tx
+            let _ = back_tx
                 .send(BackendMessage::SubscriptionsDeleted(results))
-                .await
-                .unwrap();
+                .await;
         });
     }
 
-    fn stream_messages(
-        &self,
-        topic_name: TopicName,
-        sub_name: SubscriptionName,
-        cancel_token: CancellationToken,
-    ) {
+    fn list_created_subscriptions(&self) {
         let back_tx = self.back_tx.clone();
-        let client = self.client.clone();
+        let admin_conns = self.admin_conns.clone();
+        let project = format!("projects/{}", self.project_id);
 
         self.rt.spawn(async move {
-            let subscription = client.subscription(&sub_name.0);
+            let mut created_subscriptions = vec![];
+            let mut page_token = String::new();
 
-            let pull_messages_future = async move {
-                match subscription.subscribe(None).await {
-                    Ok(mut stream) => {
-                        while let Some(message) = stream.next().await {
-                            let _ = message.ack().await;
-
-                            back_tx
-                                .send(BackendMessage::MessageReceived(
-                                    topic_name.clone(),
-                                    message.into(),
-                                ))
-                                .await
-                                .unwrap();
+            let message = loop {
+                let request = create_request(
+                    format!("project={}", project),
+                    ListSubscriptionsRequest {
+                        project: project.clone(),
+                        page_size: 1000,
+                        page_token: page_token.clone(),
+                    },
+                );
+
+                match SubscriptionAdminClient::new(admin_conns.conn())
+                    .list_subscriptions(request)
+                    .await
+                {
+                    Ok(response) => {
+                        let response = response.into_inner();
+
+                        created_subscriptions.extend(
+                            response
+                                .subscriptions
+                                .into_iter()
+                                .filter_map(CreatedSubscription::from_subscription),
+                        );
+
+                        if response.next_page_token.is_empty() {
+                            break BackendMessage::CreatedSubscriptionsListed(
+                                created_subscriptions,
+                            );
                         }
+
+                        page_token = response.next_page_token;
                     }
                     Err(status) => {
                         eprintln!("{}", status);
-                        back_tx
-                            .send(BackendMessage::Error(BackendError::StreamMessagesFailed(
-                                topic_name, sub_name,
-                            )))
-                            .await
-                            .unwrap()
+                        break BackendMessage::Error(BackendError::ListCreatedSubscriptionsFailed);
                     }
-                };
+                }
             };
 
+            let _ = back_tx.send(message).await;
+        });
+    }
+
+    fn validate_subscriptions(&self, subscriptions: Vec<(TopicName, SubscriptionName)>) {
+        let back_tx = self.back_tx.clone();
+        let client = self.client.clone();
+
+        self.rt.spawn(async move {
+            let futures = subscriptions.into_iter().map(|(topic_name, sub_name)| {
+                let client = client.clone();
+                let back_tx = back_tx.clone();
+                async move {
+                    match subscription_exists(&client, &topic_name, &sub_name).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            let _ = back_tx
+                                .send(BackendMessage::SubscriptionLost(topic_name, sub_name))
+                                .await;
+                        }
+                        // Leave the Subscription be if it can't be checked, such as when
+                        // offline, as streaming from it will report any problem.
+                        Err(status) => eprintln!("{}", status),
+                    }
+                }
+            });
+
+            futures::future::join_all(futures).await;
+        });
+    }
+
+    fn stream_messages(
+        &self,
+        topic_name: TopicName,
+        sub_name: SubscriptionName,
+        cancel_token: CancellationToken,
+    ) {
+        let back_tx = self.back_tx.clone();
+        let client = self.client.clone();
+
+        self.rt.spawn(async move {
             select! {
               _ = cancel_token.cancelled() => {}
-              _ = pull_messages_future => {}
+              _ = stream::stream_messages(&client, &back_tx, topic_name, sub_name) => {}
+            }
+        });
+    }
+
+    /// Starts the push receiver on the port, unless it is already running there.
+    fn start_push_receiver(&mut self, port: u16) -> bool {
+        if self
+            .push_receiver
+            .as_ref()
+            .is_some_and(|push_receiver| push_receiver.port == port)
+        {
+            return true;
+        }
+
+        // Stop any receiver on a different port first.
+        self.push_receiver = None;
+
+        let _guard = self.rt.enter();
+
+        match PushReceiver::start(port, self.push_routes.clone(), self.back_tx.clone()) {
+            Ok(push_receiver) => {
+                self.push_receiver = Some(push_receiver);
+                true
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                let back_tx = self.back_tx.clone();
+                self.rt.spawn(async move {
+                    let _ = back_tx
+                        .send(BackendMessage::Error(BackendError::PushReceiverFailed(
+                            port,
+                        )))
+                        .await;
+                });
+                false
+            }
+        }
+    }
+
+    fn create_push_subscription(&mut self, topic_name: TopicName, port: u16) {
+        if !self.start_push_receiver(port) {
+            return;
+        }
+
+        let back_tx = self.back_tx.clone();
+        let client = self.client.clone();
+        let push_routes = self.push_routes.clone();
+
+        self.rt.spawn(async move {
+            let config = SubscriptionConfig {
+                push_config: Some(PushConfig {
+                    push_endpoint: push_endpoint(port),
+                    ..Default::default()
+                }),
+                labels: created_subscription_labels(),
+                expiration_policy: Some(created_subscription_expiration_policy()),
+                ..Default::default()
+            };
+
+            let message = match client
+                .create_subscription(
+                    &format!("pubsubman-push-subscription-{}", Uuid::new_v4()),
+                    &topic_name.0,
+                    config,
+                    None,
+                )
+                .await
+            {
+                Ok(subscription) => {
+                    let sub_name = SubscriptionName(subscription.fully_qualified_name().to_owned());
+
+                    push_routes
+                        .lock()
+                        .unwrap()
+                        .insert(sub_name.clone(), topic_name.clone());
+
+                    BackendMessage::PushSubscriptionCreated(topic_name, sub_name)
+                }
+                Err(status) => {
+                    eprintln!("{}", status);
+                    BackendMessage::Error(BackendError::CreatePushSubscriptionFailed(topic_name))
+                }
+            };
+
+            let _ = back_tx.send(message).await;
+        });
+    }
+
+    /// Points the existing push Subscription at the push receiver, in case the port has
+    /// changed since it was created, before receiving its messages.
+    fn receive_push_messages(
+        &mut self,
+        topic_name: TopicName,
+        sub_name: SubscriptionName,
+        port: u16,
+    ) {
+        if !self.start_push_receiver(port) {
+            return;
+        }
+
+        let back_tx = self.back_tx.clone();
+        let client = self.client.clone();
+        let push_routes = self.push_routes.clone();
+
+        self.rt.spawn(async move {
+            let subscription = client.subscription(&sub_name.0);
+            let update = SubscriptionConfigToUpdate {
+                push_config: Some(PushConfig {
+                    push_endpoint: push_endpoint(port),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+
+            match subscription.update(update, None).await {
+                Ok(_) => {
+                    push_routes.lock().unwrap().insert(sub_name, topic_name);
+                }
+                Err(status) => {
+                    eprintln!("{}", status);
+
+                    let message = if status.code() == Code::NotFound {
+                        BackendMessage::SubscriptionLost(topic_name, sub_name)
+                    } else {
+                        BackendMessage::Error(BackendError::PushMessagesFailed(
+                            topic_name, sub_name,
+                        ))
+                    };
+
+                    let _ = back_tx.send(message).await;
+                }
             }
         });
     }
@@ -220,24 +473,154 @@ impl Backend {
         self.rt.spawn(async move {
             let topic = client.topic(&topic_name.0);
             let publisher = topic.new_publisher(None);
-            let awaiter = publisher.publish(message.into()).await;
+            let awaiter = publisher.publish(message.clone().into()).await;
+
+            let message = match awaiter.get().await {
+                Ok(message_id) => BackendMessage::MessagePublished(topic_name, message, message_id),
+                Err(status) => {
+                    eprintln!("{}", status);
+                    BackendMessage::Error(BackendError::PublishMessageFailed(topic_name))
+                }
+            };
+
+            let _ = back_tx.send(message).await;
+        });
+    }
+
+    fn get_topic_metadata(&self, topic_name: TopicName) {
+        let back_tx = self.back_tx.clone();
+        let admin_conns = self.admin_conns.clone();
+
+        self.rt.spawn(async move {
+            let request = create_request(
+                format!("topic={}", topic_name.0),
+                GetTopicRequest {
+                    topic: topic_name.0.clone(),
+                },
+            );
+
+            let message = match TopicAdminClient::new(admin_conns.conn())
+                .get_topic(request)
+                .await
+            {
+                Ok(response) => BackendMessage::TopicMetadataFetched(
+                    topic_name,
+                    TopicMetadata::from(response.into_inner()),
+                ),
+                Err(status) => {
+                    eprintln!("{}", status);
+                    BackendMessage::Error(BackendError::GetTopicMetadataFailed(topic_name))
+                }
+            };
+
+            let _ = back_tx.send(message).await;
+        });
+    }
+
+    fn get_subscription_metadata(&self, topic_name: TopicName, sub_name: SubscriptionName) {
+        let back_tx = self.back_tx.clone();
+        let client = self.client.clone();
 
-            if let Err(status) = awaiter.get().await {
-                eprintln!("{}", status);
-                back_tx
-                    .send(BackendMessage::Error(BackendError::PublishMessageFailed(
+        self.rt.spawn(async move {
+            let subscription = client.subscription(&sub_name.0);
+
+            let message = match subscription.config(None).await {
+                Ok((topic, config)) => BackendMessage::SubscriptionMetadataFetched(
+                    topic_name,
+                    SubscriptionMetadata::new(topic, config),
+                ),
+                Err(status) => {
+                    eprintln!("{}", status);
+                    BackendMessage::Error(BackendError::GetSubscriptionMetadataFailed(topic_name))
+                }
+            };
+
+            let _ = back_tx.send(message).await;
+        });
+    }
+
+    fn update_topic(&self, topic_name: TopicName, update: TopicUpdate) {
+        let back_tx = self.back_tx.clone();
+        let admin_conns = self.admin_conns.clone();
+
+        self.rt.spawn(async move {
+            let request = create_request(
+                format!("topic.name={}", topic_name.0),
+                update.into_request(&topic_name),
+            );
+
+            let message = match TopicAdminClient::new(admin_conns.conn())
+                .update_topic(request)
+                .await
+            {
+                Ok(response) => BackendMessage::TopicMetadataUpdated(
+                    topic_name,
+                    TopicMetadata::from(response.into_inner()),
+                ),
+                Err(status) => {
+                    eprintln!("{}", status);
+                    BackendMessage::Error(BackendError::UpdateTopicFailed(topic_name))
+                }
+            };
+
+            let _ = back_tx.send(message).await;
+        });
+    }
+
+    fn update_subscription(
+        &self,
+        topic_name: TopicName,
+        sub_name: SubscriptionName,
+        update: SubscriptionUpdate,
+    ) {
+        let back_tx = self.back_tx.clone();
+        let admin_conns = self.admin_conns.clone();
+
+        self.rt.spawn(async move {
+            let request = create_request(
+                format!("subscription.name={}", sub_name.0),
+                update.into_request(&sub_name),
+            );
+
+            let message = match SubscriptionAdminClient::new(admin_conns.conn())
+                .update_subscription(request)
+                .await
+            {
+                Ok(response) => {
+                    let subscription = response.into_inner();
+                    BackendMessage::SubscriptionMetadataUpdated(
                         topic_name,
-                    )))
-                    .await
-                    .unwrap()
-            }
+                        SubscriptionMetadata::new(subscription.topic.clone(), subscription.into()),
+                    )
+                }
+                Err(status) => {
+                    eprintln!("{}", status);
+                    BackendMessage::Error(BackendError::UpdateSubscriptionFailed(topic_name))
+                }
+            };
+
+            let _ = back_tx.send(message).await;
         });
     }
 }
 
+/// Whether the Subscription still exists and is attached to the Topic, which it may not be after
+/// being deleted, expiring from inactivity, or its Topic being deleted.
+async fn subscription_exists(
+    client: &Client,
+    topic_name: &TopicName,
+    sub_name: &SubscriptionName,
+) -> Result<bool, Status> {
+    match client.subscription(&sub_name.0).config(None).await {
+        Ok((topic, config)) => Ok(topic == topic_name.0 && !config.detached),
+        Err(status) if status.code() == Code::NotFound => Ok(false),
+        Err(status) => Err(status),
+    }
+}
+
 async fn create_client(
     emulator_project_id: Option<String>,
-) -> Result<(Client, String), Box<dyn Error>> {
+) -> Result<(Client, ConnectionManager, String), Box<dyn Error>> {
     let mut config = ClientConfig::default().with_auth().await?;
 
     if let (Environment::Emulator(_), Some(emulator_project_id)) =
@@ -248,5 +631,13 @@ async fn create_client(
 
     let project_id = config.project_id.clone();
 
-    Ok((Client::new(config).await?, project_id.unwrap()))
+    let admin_conns = ConnectionManager::new(
+        1,
+        &config.endpoint,
+        &config.environment,
+        &ConnectionOptions::default(),
+    )
+    .await?;
+
+    Ok((Client::new(config).await?, admin_conns, project_id.unwrap()))
 }
@@ -1,23 +1,53 @@
 use tokio_util::sync::CancellationToken;
 
-use crate::model::{PubsubMessage, PubsubMessageToPublish, SubscriptionName, TopicName};
+use crate::model::{
+    CreatedSubscription, PubsubMessage, PubsubMessageToPublish, StreamState, SubscriptionFilter,
+    SubscriptionMetadata, SubscriptionName, SubscriptionUpdate, TopicMetadata, TopicName,
+    TopicUpdate,
+};
 
 #[derive(Debug)]
 pub enum FrontendMessage {
     RefreshTopicsRequest,
-    CreateSubscriptionRequest(TopicName),
+    CreateSubscriptionRequest(TopicName, Option<SubscriptionFilter>),
     DeleteSubscriptions(Vec<SubscriptionName>),
     StreamMessages(TopicName, SubscriptionName, CancellationToken),
     PublishMessage(TopicName, PubsubMessageToPublish),
+    GetTopicMetadata(TopicName),
+    GetSubscriptionMetadata(TopicName, SubscriptionName),
+    UpdateTopic(TopicName, TopicUpdate),
+    UpdateSubscription(TopicName, SubscriptionName, SubscriptionUpdate),
+    /// Creates a Subscription that pushes messages to the local push receiver on the port.
+    CreatePushSubscriptionRequest(TopicName, u16),
+    ReceivePushMessages(TopicName, SubscriptionName, u16),
+    StopPushMessages(SubscriptionName),
+    /// Lists the Subscriptions created by this app across the project, by any host.
+    ListCreatedSubscriptions,
+    /// Checks that the stored Subscriptions still exist, reporting any that were lost.
+    ValidateSubscriptions(Vec<(TopicName, SubscriptionName)>),
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum BackendMessage {
     ClientInitialised(String),
     TopicsUpdated(Vec<TopicName>),
-    SubscriptionCreated(TopicName, SubscriptionName),
-    MessageReceived(TopicName, PubsubMessage),
+    SubscriptionCreated(TopicName, SubscriptionName, Option<SubscriptionFilter>),
+    PushSubscriptionCreated(TopicName, SubscriptionName),
+    /// Messages received together, in the order they were received.
+    MessagesReceived(TopicName, Vec<PubsubMessage>),
+    StreamStateChanged(TopicName, SubscriptionName, StreamState),
+    /// The message was published, and given the message ID.
+    MessagePublished(TopicName, PubsubMessageToPublish, String),
     SubscriptionsDeleted(Vec<Result<SubscriptionName, SubscriptionName>>),
+    /// The Subscription no longer exists, or was detached from its Topic, such as after being
+    /// deleted elsewhere or expiring from inactivity.
+    SubscriptionLost(TopicName, SubscriptionName),
+    CreatedSubscriptionsListed(Vec<CreatedSubscription>),
+    TopicMetadataFetched(TopicName, TopicMetadata),
+    SubscriptionMetadataFetched(TopicName, SubscriptionMetadata),
+    TopicMetadataUpdated(TopicName, TopicMetadata),
+    SubscriptionMetadataUpdated(TopicName, SubscriptionMetadata),
     Error(BackendError),
 }
 
@@ -29,8 +59,25 @@ pub enum BackendError {
     GetTopicsFailed,
     #[error("Failed to create Subscription for {0}.")]
     CreateSubscriptionFailed(TopicName),
+    /// The stream of messages ended too many times in a row, and is no longer being resumed.
     #[error("Failed to get messages from {0}.")]
     StreamMessagesFailed(TopicName, SubscriptionName),
+    #[error("Failed to create push Subscription for {0}.")]
+    CreatePushSubscriptionFailed(TopicName),
+    #[error("Failed to receive pushed messages from {0}.")]
+    PushMessagesFailed(TopicName, SubscriptionName),
+    #[error("Failed to start the push receiver on port {0}.")]
+    PushReceiverFailed(u16),
+    #[error("Failed to list the Subscriptions created by pubsubman.")]
+    ListCreatedSubscriptionsFailed,
     #[error("Failed to publish message to {0}.")]
     PublishMessageFailed(TopicName),
+    #[error("Failed to get the details of {0}.")]
+    GetTopicMetadataFailed(TopicName),
+    #[error("Failed to get the Subscription details for {0}.")]
+    GetSubscriptionMetadataFailed(TopicName),
+    #[error("Failed to update {0}.")]
+    UpdateTopicFailed(TopicName),
+    #[error("Failed to update the Subscription for {0}.")]
+    UpdateSubscriptionFailed(TopicName),
 }
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use google_cloud_googleapis::pubsub::v1::{ExpirationPolicy, Subscription};
+use prost_types::Duration;
+
+use super::{SubscriptionName, TopicName};
+
+const CREATED_BY_LABEL: &str = "created-by";
+const CREATED_BY_VALUE: &str = "pubsubman";
+const HOST_LABEL: &str = "created-on-host";
+const CREATED_AT_LABEL: &str = "created-at";
+
+/// The prefix of the IDs of Subscriptions created before they were labelled.
+const LEGACY_ID_PREFIX: &str = "pubsubman-";
+
+/// Created Subscriptions are deleted by Pub/Sub after being inactive for this long, in case
+/// they are not deleted when quitting, which is the shortest expiration that it allows.
+const EXPIRATION_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// The maximum length of a label value.
+const MAX_LABEL_LEN: usize = 63;
+
+/// A Subscription created by this app, on this or any other host.
+#[derive(Debug, Clone)]
+pub struct CreatedSubscription {
+    pub name: SubscriptionName,
+    pub topic_name: TopicName,
+    pub host: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl CreatedSubscription {
+    /// Whether the label is one of those that identify a Subscription as created by this app.
+    pub fn is_label(key: &str) -> bool {
+        [CREATED_BY_LABEL, HOST_LABEL, CREATED_AT_LABEL].contains(&key)
+    }
+
+    /// Returns `None` if the Subscription was not created by this app.
+    pub(crate) fn from_subscription(subscription: Subscription) -> Option<Self> {
+        let labels = &subscription.labels;
+        let is_labelled =
+            labels.get(CREATED_BY_LABEL).map(String::as_str) == Some(CREATED_BY_VALUE);
+        let is_legacy = subscription
+            .name
+            .rsplit_once("/subscriptions/")
+            .is_some_and(|(_, id)| id.starts_with(LEGACY_ID_PREFIX));
+
+        if !is_labelled && !is_legacy {
+            return None;
+        }
+
+        Some(Self {
+            host: labels.get(HOST_LABEL).cloned(),
+            created_at: labels
+                .get(CREATED_AT_LABEL)
+                .and_then(|created_at| created_at.parse().ok())
+                .and_then(|created_at| DateTime::from_timestamp(created_at, 0)),
+            name: SubscriptionName(subscription.name),
+            topic_name: TopicName(subscription.topic),
+        })
+    }
+}
+
+/// The labels that identify a Subscription as created by this app, on this host, now.
+pub(crate) fn created_subscription_labels() -> HashMap<String, String> {
+    let mut labels = HashMap::from([
+        (CREATED_BY_LABEL.to_string(), CREATED_BY_VALUE.to_string()),
+        (
+            CREATED_AT_LABEL.to_string(),
+            Utc::now().timestamp().to_string(),
+        ),
+    ]);
+
+    if let Some(host) = hostname().map(|host| label_value(&host)) {
+        labels.insert(HOST_LABEL.to_string(), host);
+    }
+
+    labels
+}
+
+pub(crate) fn created_subscription_expiration_policy() -> ExpirationPolicy {
+    ExpirationPolicy {
+        ttl: Some(Duration {
+            seconds: EXPIRATION_TTL_SECS,
+            nanos: 0,
+        }),
+    }
+}
+
+fn hostname() -> Option<String> {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+}
+
+/// Label values may only contain lowercase letters, digits, underscores and dashes.
+fn label_value(value: &str) -> String {
+    value
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .take(MAX_LABEL_LEN)
+        .collect()
+}
@@ -0,0 +1,299 @@
+use std::collections::BTreeMap;
+
+use google_cloud_googleapis::pubsub::v1::{
+    DeadLetterPolicy, Encoding, PushConfig, RetryPolicy, SchemaSettings, Subscription, Topic,
+    UpdateSubscriptionRequest, UpdateTopicRequest,
+};
+use google_cloud_pubsub::subscription::SubscriptionConfig;
+use prost_types::{Duration, FieldMask};
+
+use super::{SubscriptionName, TopicName};
+
+/// The configuration of a Topic, as shown in its details.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct TopicMetadata {
+    pub labels: BTreeMap<String, String>,
+    pub kms_key_name: Option<String>,
+    /// How long published messages are retained for, in seconds.
+    pub message_retention_duration_secs: Option<i64>,
+    /// The regions that messages may be stored in, where empty means any region.
+    pub allowed_persistence_regions: Vec<String>,
+    pub schema_settings: Option<SchemaSettingsMetadata>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct SchemaSettingsMetadata {
+    pub schema: String,
+    pub encoding: String,
+    pub first_revision_id: Option<String>,
+    pub last_revision_id: Option<String>,
+}
+
+/// The configuration of a Subscription, as shown in its details.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct SubscriptionMetadata {
+    pub topic: String,
+    pub labels: BTreeMap<String, String>,
+    pub ack_deadline_seconds: i32,
+    pub push_config: Option<PushConfigMetadata>,
+    pub filter: Option<String>,
+    pub retry_policy: Option<RetryPolicyMetadata>,
+    pub dead_letter_policy: Option<DeadLetterPolicyMetadata>,
+    /// How long unacknowledged messages are retained for, in seconds.
+    pub message_retention_duration_secs: Option<i64>,
+    pub retain_acked_messages: bool,
+    pub enable_message_ordering: bool,
+    pub enable_exactly_once_delivery: bool,
+    /// How long the Subscription may be inactive for before it is deleted, in seconds.
+    pub expiration_ttl_secs: Option<i64>,
+    pub detached: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct PushConfigMetadata {
+    pub push_endpoint: String,
+    pub attributes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct RetryPolicyMetadata {
+    pub minimum_backoff_secs: Option<i64>,
+    pub maximum_backoff_secs: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct DeadLetterPolicyMetadata {
+    pub dead_letter_topic: String,
+    pub max_delivery_attempts: i32,
+}
+
+impl From<Topic> for TopicMetadata {
+    fn from(topic: Topic) -> Self {
+        Self {
+            labels: topic.labels.into_iter().collect(),
+            kms_key_name: non_empty(topic.kms_key_name),
+            message_retention_duration_secs: topic
+                .message_retention_duration
+                .map(|duration| duration.seconds),
+            allowed_persistence_regions: topic
+                .message_storage_policy
+                .map(|policy| policy.allowed_persistence_regions)
+                .unwrap_or_default(),
+            schema_settings: topic.schema_settings.map(SchemaSettingsMetadata::from),
+        }
+    }
+}
+
+impl From<SchemaSettings> for SchemaSettingsMetadata {
+    fn from(settings: SchemaSettings) -> Self {
+        let encoding = Encoding::from_i32(settings.encoding)
+            .unwrap_or(Encoding::Unspecified)
+            .as_str_name()
+            .to_string();
+
+        Self {
+            schema: settings.schema,
+            encoding,
+            first_revision_id: non_empty(settings.first_revision_id),
+            last_revision_id: non_empty(settings.last_revision_id),
+        }
+    }
+}
+
+impl SubscriptionMetadata {
+    pub fn new(topic: String, config: SubscriptionConfig) -> Self {
+        Self {
+            topic,
+            labels: config.labels.into_iter().collect(),
+            ack_deadline_seconds: config.ack_deadline_seconds,
+            push_config: config.push_config.map(PushConfigMetadata::from),
+            filter: non_empty(config.filter),
+            retry_policy: config.retry_policy.map(RetryPolicyMetadata::from),
+            dead_letter_policy: config
+                .dead_letter_policy
+                .map(DeadLetterPolicyMetadata::from),
+            message_retention_duration_secs: config
+                .message_retention_duration
+                .map(|duration| duration.as_secs() as i64),
+            retain_acked_messages: config.retain_acked_messages,
+            enable_message_ordering: config.enable_message_ordering,
+            enable_exactly_once_delivery: config.enable_exactly_once_delivery,
+            expiration_ttl_secs: config
+                .expiration_policy
+                .and_then(|policy| policy.ttl)
+                .map(|ttl| ttl.seconds),
+            detached: config.detached,
+        }
+    }
+}
+
+impl From<PushConfig> for PushConfigMetadata {
+    fn from(config: PushConfig) -> Self {
+        Self {
+            push_endpoint: config.push_endpoint,
+            attributes: config.attributes.into_iter().collect(),
+        }
+    }
+}
+
+impl From<RetryPolicy> for RetryPolicyMetadata {
+    fn from(policy: RetryPolicy) -> Self {
+        Self {
+            minimum_backoff_secs: policy.minimum_backoff.map(|backoff| backoff.seconds),
+            maximum_backoff_secs: policy.maximum_backoff.map(|backoff| backoff.seconds),
+        }
+    }
+}
+
+impl From<DeadLetterPolicy> for DeadLetterPolicyMetadata {
+    fn from(policy: DeadLetterPolicy) -> Self {
+        Self {
+            dead_letter_topic: policy.dead_letter_topic,
+            max_delivery_attempts: policy.max_delivery_attempts,
+        }
+    }
+}
+
+/// The changes to the mutable settings of a Topic, along with the names of the fields that
+/// they change.
+#[derive(Debug, Clone)]
+pub struct TopicUpdate {
+    pub metadata: TopicMetadata,
+    pub update_mask: Vec<&'static str>,
+}
+
+impl TopicUpdate {
+    pub fn new(current: &TopicMetadata, edited: TopicMetadata) -> Self {
+        let mut update_mask = vec![];
+
+        if current.labels != edited.labels {
+            update_mask.push("labels");
+        }
+
+        if current.message_retention_duration_secs != edited.message_retention_duration_secs {
+            update_mask.push("message_retention_duration");
+        }
+
+        Self {
+            metadata: edited,
+            update_mask,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.update_mask.is_empty()
+    }
+
+    pub(crate) fn into_request(self, topic_name: &TopicName) -> UpdateTopicRequest {
+        let metadata = self.metadata;
+
+        UpdateTopicRequest {
+            topic: Some(Topic {
+                name: topic_name.0.clone(),
+                labels: metadata.labels.into_iter().collect(),
+                message_retention_duration: metadata.message_retention_duration_secs.map(duration),
+                ..Default::default()
+            }),
+            update_mask: Some(field_mask(self.update_mask)),
+        }
+    }
+}
+
+/// The changes to the mutable settings of a Subscription, along with the names of the fields
+/// that they change. The filter of a Subscription can't be changed after it is created.
+#[derive(Debug, Clone)]
+pub struct SubscriptionUpdate {
+    pub metadata: SubscriptionMetadata,
+    pub update_mask: Vec<&'static str>,
+}
+
+impl SubscriptionUpdate {
+    pub fn new(current: &SubscriptionMetadata, edited: SubscriptionMetadata) -> Self {
+        let mut update_mask = vec![];
+
+        if current.labels != edited.labels {
+            update_mask.push("labels");
+        }
+
+        if current.ack_deadline_seconds != edited.ack_deadline_seconds {
+            update_mask.push("ack_deadline_seconds");
+        }
+
+        if current.push_config != edited.push_config {
+            update_mask.push("push_config");
+        }
+
+        if current.retry_policy != edited.retry_policy {
+            update_mask.push("retry_policy");
+        }
+
+        if current.dead_letter_policy != edited.dead_letter_policy {
+            update_mask.push("dead_letter_policy");
+        }
+
+        if current.message_retention_duration_secs != edited.message_retention_duration_secs {
+            update_mask.push("message_retention_duration");
+        }
+
+        if current.retain_acked_messages != edited.retain_acked_messages {
+            update_mask.push("retain_acked_messages");
+        }
+
+        Self {
+            metadata: edited,
+            update_mask,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.update_mask.is_empty()
+    }
+
+    pub(crate) fn into_request(self, sub_name: &SubscriptionName) -> UpdateSubscriptionRequest {
+        let metadata = self.metadata;
+
+        UpdateSubscriptionRequest {
+            subscription: Some(Subscription {
+                name: sub_name.0.clone(),
+                topic: metadata.topic,
+                labels: metadata.labels.into_iter().collect(),
+                ack_deadline_seconds: metadata.ack_deadline_seconds,
+                // A Subscription without a push config is a pull Subscription.
+                push_config: metadata.push_config.map(|push_config| PushConfig {
+                    push_endpoint: push_config.push_endpoint,
+                    attributes: push_config.attributes.into_iter().collect(),
+                    ..Default::default()
+                }),
+                retry_policy: metadata.retry_policy.map(|retry_policy| RetryPolicy {
+                    minimum_backoff: retry_policy.minimum_backoff_secs.map(duration),
+                    maximum_backoff: retry_policy.maximum_backoff_secs.map(duration),
+                }),
+                dead_letter_policy: metadata.dead_letter_policy.map(|dead_letter_policy| {
+                    DeadLetterPolicy {
+                        dead_letter_topic: dead_letter_policy.dead_letter_topic,
+                        max_delivery_attempts: dead_letter_policy.max_delivery_attempts,
+                    }
+                }),
+                message_retention_duration: metadata.message_retention_duration_secs.map(duration),
+                retain_acked_messages: metadata.retain_acked_messages,
+                ..Default::default()
+            }),
+            update_mask: Some(field_mask(self.update_mask)),
+        }
+    }
+}
+
+fn duration(seconds: i64) -> Duration {
+    Duration { seconds, nanos: 0 }
+}
+
+fn field_mask(paths: Vec<&'static str>) -> FieldMask {
+    FieldMask {
+        paths: paths.into_iter().map(str::to_string).collect(),
+    }
+}
+
+/// Pub/Sub represents unset strings as empty.
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
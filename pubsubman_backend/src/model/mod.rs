@@ -1,16 +1,41 @@
+mod created_subscription;
+mod metadata;
 mod pubsub_message;
 mod pubsub_message_to_publish;
+mod push_envelope;
+mod stream_state;
+mod subscription_filter;
 
 use std::fmt::Display;
 
+pub use created_subscription::CreatedSubscription;
+pub(crate) use created_subscription::{
+    created_subscription_expiration_policy, created_subscription_labels,
+};
+pub use metadata::{
+    DeadLetterPolicyMetadata, PushConfigMetadata, RetryPolicyMetadata, SchemaSettingsMetadata,
+    SubscriptionMetadata, SubscriptionUpdate, TopicMetadata, TopicUpdate,
+};
 pub use pubsub_message::PubsubMessage;
 pub use pubsub_message_to_publish::PubsubMessageToPublish;
+pub(crate) use push_envelope::PushEnvelope;
+pub use stream_state::StreamState;
+pub use subscription_filter::SubscriptionFilter;
 
 #[derive(
     Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, serde::Deserialize, serde::Serialize,
 )]
 pub struct TopicName(pub String);
 
+impl TopicName {
+    /// The Topic ID, without the `projects/{project}/topics/` prefix of the full name.
+    pub fn short_id(&self) -> &str {
+        self.0
+            .rsplit_once("/topics/")
+            .map_or(self.0.as_str(), |(_, id)| id)
+    }
+}
+
 impl Display for TopicName {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -11,10 +11,20 @@ pub struct PubsubMessage {
     pub data_json: Value,
     pub attributes: HashMap<String, String>,
     pub attributes_json: Value,
+    /// When this app received the message.
+    #[serde(default)]
+    pub received_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub ordering_key: String,
+    /// The approximate number of delivery attempts, when the subscription has a dead letter policy.
+    #[serde(default)]
+    pub delivery_attempt: Option<usize>,
 }
 
 impl From<ReceivedMessage> for PubsubMessage {
     fn from(value: ReceivedMessage) -> Self {
+        let delivery_attempt = value.delivery_attempt();
+
         let publish_time = value
             .message
             .publish_time
@@ -26,26 +36,48 @@ impl From<ReceivedMessage> for PubsubMessage {
 
         let data = str::from_utf8(&value.message.data).unwrap().to_string();
 
+        Self {
+            received_at: Some(Utc::now()),
+            ordering_key: value.message.ordering_key,
+            delivery_attempt,
+            ..Self::new(
+                value.message.message_id,
+                publish_time,
+                data,
+                value.message.attributes,
+            )
+        }
+    }
+}
+
+impl PubsubMessage {
+    pub fn new(
+        id: String,
+        publish_time: Option<DateTime<Utc>>,
+        data: String,
+        attributes: HashMap<String, String>,
+    ) -> Self {
         let data_json: Value = match serde_json::from_str(&data) {
             Ok(val) => val,
             Err(_) => Value::String(data.clone()),
         };
 
         let attributes_json = Value::Object(Map::from_iter(
-            value
-                .message
-                .attributes
+            attributes
                 .iter()
                 .map(|(k, v)| (k.to_owned(), Value::String(v.to_owned()))),
         ));
 
         Self {
-            id: value.message.message_id,
+            id,
             publish_time,
             data,
             data_json,
-            attributes: value.message.attributes,
+            attributes,
             attributes_json,
+            received_at: None,
+            ordering_key: String::new(),
+            delivery_attempt: None,
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PubsubMessageToPublish {
     data: String,
     attributes: HashMap<String, String>,
@@ -10,6 +10,14 @@ impl PubsubMessageToPublish {
     pub fn new(data: String, attributes: HashMap<String, String>) -> Self {
         Self { data, attributes }
     }
+
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    pub fn attributes(&self) -> &HashMap<String, String> {
+        &self.attributes
+    }
 }
 
 impl From<PubsubMessageToPublish> for google_cloud_googleapis::pubsub::v1::PubsubMessage {
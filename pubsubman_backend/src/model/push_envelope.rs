@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::{DateTime, Utc};
+
+use super::{PubsubMessage, SubscriptionName};
+
+/// The body of a request that Pub/Sub makes to deliver a message to a push Subscription's
+/// endpoint, see <https://cloud.google.com/pubsub/docs/push#receive_push>.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PushEnvelope {
+    message: PushedMessage,
+    subscription: String,
+    #[serde(default)]
+    delivery_attempt: Option<usize>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PushedMessage {
+    /// The message data, encoded as base64.
+    #[serde(default)]
+    data: String,
+    #[serde(default)]
+    attributes: HashMap<String, String>,
+    message_id: String,
+    #[serde(default)]
+    publish_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    ordering_key: String,
+}
+
+impl PushEnvelope {
+    /// Returns the pushed message, along with the Subscription it was pushed to.
+    pub(crate) fn into_message(self) -> Result<(SubscriptionName, PubsubMessage), String> {
+        let data = STANDARD
+            .decode(&self.message.data)
+            .map_err(|err| format!("Failed to decode message data: {}", err))?;
+
+        let message = PubsubMessage {
+            received_at: Some(Utc::now()),
+            ordering_key: self.message.ordering_key,
+            delivery_attempt: self.delivery_attempt,
+            ..PubsubMessage::new(
+                self.message.message_id,
+                self.message.publish_time,
+                String::from_utf8_lossy(&data).into_owned(),
+                self.message.attributes,
+            )
+        };
+
+        Ok((SubscriptionName(self.subscription), message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(body: &str) -> Result<(SubscriptionName, PubsubMessage), String> {
+        serde_json::from_str::<PushEnvelope>(body)
+            .unwrap()
+            .into_message()
+    }
+
+    #[test]
+    fn decodes_the_message() {
+        let (sub_name, message) = decode(
+            r#"{
+                "message": {
+                    "attributes": {"type": "order"},
+                    "data": "eyJ0b3RhbCI6IDEwMH0=",
+                    "messageId": "2070443601311540",
+                    "publishTime": "2021-02-26T19:13:55.749Z",
+                    "orderingKey": "customer-1"
+                },
+                "subscription": "projects/test/subscriptions/test",
+                "deliveryAttempt": 2
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(sub_name.0, "projects/test/subscriptions/test");
+        assert_eq!(message.id, "2070443601311540");
+        assert_eq!(message.data, r#"{"total": 100}"#);
+        assert_eq!(message.data_json["total"], 100);
+        assert_eq!(message.attributes["type"], "order");
+        assert_eq!(message.ordering_key, "customer-1");
+        assert_eq!(message.delivery_attempt, Some(2));
+        assert_eq!(
+            message
+                .publish_time
+                .map(|publish_time| publish_time.timestamp_millis()),
+            Some(1_614_366_835_749)
+        );
+    }
+
+    #[test]
+    fn decodes_a_message_without_data() {
+        let (_, message) = decode(
+            r#"{
+                "message": {"messageId": "1"},
+                "subscription": "projects/test/subscriptions/test"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(message.data, "");
+        assert!(message.attributes.is_empty());
+        assert_eq!(message.delivery_attempt, None);
+    }
+
+    #[test]
+    fn decodes_invalid_utf8_lossily() {
+        let (_, message) = decode(
+            r#"{
+                "message": {"messageId": "1", "data": "/w=="},
+                "subscription": "projects/test/subscriptions/test"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(message.data, "\u{FFFD}");
+    }
+
+    #[test]
+    fn rejects_data_that_is_not_base64() {
+        let result = decode(
+            r#"{
+                "message": {"messageId": "1", "data": "not base64!"},
+                "subscription": "projects/test/subscriptions/test"
+            }"#,
+        );
+
+        assert!(result.is_err());
+    }
+}
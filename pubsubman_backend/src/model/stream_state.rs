@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+/// The state of a stream of messages from a Subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamState {
+    /// Checking the Subscription and opening the stream.
+    Connecting,
+    Streaming,
+    /// Messages are arriving faster than the UI is taking them, so they are being held back.
+    Stalled,
+    /// The stream ended unexpectedly, and will be resumed after the delay. The attempt counts
+    /// the consecutive times the stream has ended.
+    Resuming {
+        attempt: u32,
+        delay: Duration,
+    },
+}
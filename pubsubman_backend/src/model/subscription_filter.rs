@@ -0,0 +1,287 @@
+use std::{fmt::Display, iter::Peekable, str::CharIndices};
+
+/// The maximum length of a filter, in bytes, that Pub/Sub accepts.
+const MAX_FILTER_LEN: usize = 256;
+
+/// A filter on message attributes, that a Subscription only delivers matching messages for, e.g:
+///
+/// ```text
+/// attributes.type = "order" AND NOT attributes:test
+/// ```
+///
+/// See <https://cloud.google.com/pubsub/docs/subscription-message-filter#filtering_syntax>.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub struct SubscriptionFilter(String);
+
+impl SubscriptionFilter {
+    /// Validates the syntax of the filter, so that malformed filters are caught before
+    /// attempting to create a Subscription with it.
+    pub fn parse(filter: &str) -> Result<Self, String> {
+        let filter = filter.trim();
+
+        if filter.len() > MAX_FILTER_LEN {
+            return Err(format!(
+                "Filter must be at most {} bytes, but is {} bytes.",
+                MAX_FILTER_LEN,
+                filter.len()
+            ));
+        }
+
+        let mut parser = Parser {
+            tokens: tokenize(filter)?.into_iter().peekable(),
+        };
+
+        parser.parse_filter()?;
+
+        match parser.tokens.next() {
+            Some(token) => Err(format!("Unexpected {}.", token)),
+            None => Ok(Self(filter.to_string())),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for SubscriptionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    String(String),
+    Dot,
+    Colon,
+    Comma,
+    Minus,
+    Eq,
+    Ne,
+    LeftParen,
+    RightParen,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(ident) => write!(f, "'{}'", ident),
+            Token::String(string) => write!(f, "\"{}\"", string),
+            Token::Dot => write!(f, "'.'"),
+            Token::Colon => write!(f, "':'"),
+            Token::Comma => write!(f, "','"),
+            Token::Minus => write!(f, "'-'"),
+            Token::Eq => write!(f, "'='"),
+            Token::Ne => write!(f, "'!='"),
+            Token::LeftParen => write!(f, "'('"),
+            Token::RightParen => write!(f, "')'"),
+        }
+    }
+}
+
+fn tokenize(filter: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = filter.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '.' => Token::Dot,
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            '-' => Token::Minus,
+            '=' => Token::Eq,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '!' if chars.next_if(|(_, c)| *c == '=').is_some() => Token::Ne,
+            '"' => Token::String(tokenize_string(start, &mut chars)?),
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = String::from(c);
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    ident.push(c);
+                }
+                Token::Ident(ident)
+            }
+            c => return Err(format!("Unexpected '{}' at position {}.", c, start)),
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn tokenize_string(start: usize, chars: &mut Peekable<CharIndices<'_>>) -> Result<String, String> {
+    let mut string = String::new();
+
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '"' => return Ok(string),
+            '\\' => match chars.next() {
+                Some((_, c)) => string.push(c),
+                None => break,
+            },
+            c => string.push(c),
+        }
+    }
+
+    Err(format!("Unterminated string at position {}.", start))
+}
+
+struct Parser {
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    /// Pub/Sub does not define a precedence between `AND` and `OR`, so they may not be mixed
+    /// without parentheses.
+    fn parse_filter(&mut self) -> Result<(), String> {
+        self.parse_term()?;
+
+        let mut operator: Option<String> = None;
+
+        while let Some(Token::Ident(ident)) = self.tokens.next_if(
+            |token| matches!(token, Token::Ident(ident) if ident == "AND" || ident == "OR"),
+        ) {
+            match &operator {
+                Some(operator) if *operator != ident => {
+                    return Err("AND and OR cannot be mixed without parentheses.".to_string());
+                }
+                _ => operator = Some(ident),
+            }
+
+            self.parse_term()?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_term(&mut self) -> Result<(), String> {
+        if self.tokens.next_if_eq(&Token::Minus).is_some() || self.next_if_ident("NOT") {
+            return self.parse_term();
+        }
+
+        match self.tokens.next() {
+            Some(Token::LeftParen) => {
+                self.parse_filter()?;
+                self.expect(Token::RightParen)
+            }
+            Some(Token::Ident(ident)) if ident == "attributes" => match self.tokens.next() {
+                Some(Token::Colon) => self.parse_key(),
+                Some(Token::Dot) => {
+                    self.parse_key()?;
+                    match self.tokens.next() {
+                        Some(Token::Eq | Token::Ne) => self.parse_string(),
+                        Some(token) => Err(format!("Expected '=' or '!=' but found {}.", token)),
+                        None => Err("Expected '=' or '!='.".to_string()),
+                    }
+                }
+                Some(token) => Err(format!(
+                    "Expected '.' or ':' after 'attributes' but found {}.",
+                    token
+                )),
+                None => Err("Expected '.' or ':' after 'attributes'.".to_string()),
+            },
+            Some(Token::Ident(ident)) if ident == "hasPrefix" => {
+                self.expect(Token::LeftParen)?;
+                self.expect(Token::Ident("attributes".to_string()))?;
+                self.expect(Token::Dot)?;
+                self.parse_key()?;
+                self.expect(Token::Comma)?;
+                self.parse_string()?;
+                self.expect(Token::RightParen)
+            }
+            Some(token) => Err(format!(
+                "Unexpected {}. Expected 'attributes', 'hasPrefix' or '('.",
+                token
+            )),
+            None => Err("Unexpected end of filter.".to_string()),
+        }
+    }
+
+    fn parse_key(&mut self) -> Result<(), String> {
+        match self.tokens.next() {
+            Some(Token::Ident(_) | Token::String(_)) => Ok(()),
+            Some(token) => Err(format!("Expected an attribute key but found {}.", token)),
+            None => Err("Expected an attribute key.".to_string()),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<(), String> {
+        match self.tokens.next() {
+            Some(Token::String(_)) => Ok(()),
+            Some(token) => Err(format!("Expected a quoted string but found {}.", token)),
+            None => Err("Expected a quoted string.".to_string()),
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.tokens.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("Expected {} but found {}.", expected, token)),
+            None => Err(format!("Expected {}.", expected)),
+        }
+    }
+
+    fn next_if_ident(&mut self, keyword: &str) -> bool {
+        self.tokens
+            .next_if(|token| matches!(token, Token::Ident(ident) if ident == keyword))
+            .is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A valid filter whose value is repeated to make the filter the given length in bytes.
+    fn filter_of_len(value: &str, len: usize) -> String {
+        let filter = format!(
+            "attributes.key = \"{}\"",
+            value.repeat((len - 19) / value.len())
+        );
+        assert_eq!(filter.len(), len);
+        filter
+    }
+
+    #[test]
+    fn and_or_cannot_be_mixed_without_parentheses() {
+        for filter in [
+            r#"attributes:a AND attributes:b OR attributes:c"#,
+            r#"attributes:a OR attributes:b AND attributes:c"#,
+            r#"(attributes:a OR attributes:b AND attributes:c)"#,
+        ] {
+            assert!(SubscriptionFilter::parse(filter).is_err(), "{}", filter);
+        }
+
+        for filter in [
+            r#"attributes:a AND attributes:b AND attributes:c"#,
+            r#"attributes:a OR attributes:b OR attributes:c"#,
+            r#"attributes:a AND (attributes:b OR attributes:c)"#,
+            r#"(attributes:a AND attributes:b) OR NOT attributes:c"#,
+            r#"-(attributes:a OR attributes:b) AND hasPrefix(attributes.c, "x")"#,
+        ] {
+            assert!(SubscriptionFilter::parse(filter).is_ok(), "{}", filter);
+        }
+    }
+
+    #[test]
+    fn filters_are_limited_to_256_bytes() {
+        assert!(SubscriptionFilter::parse(&filter_of_len("a", 256)).is_ok());
+        assert!(SubscriptionFilter::parse(&filter_of_len("a", 257)).is_err());
+
+        // The limit is in bytes rather than characters.
+        assert!(SubscriptionFilter::parse(&filter_of_len("é", 257)).is_err());
+
+        // Surrounding whitespace is trimmed before the limit is checked.
+        let padded = format!("  {}  ", filter_of_len("a", 256));
+        assert_eq!(
+            SubscriptionFilter::parse(&padded).unwrap().as_str(),
+            padded.trim()
+        );
+    }
+}
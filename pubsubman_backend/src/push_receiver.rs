@@ -0,0 +1,125 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use hyper::{
+    Body, Method, Request, Response, Server, StatusCode,
+    service::{make_service_fn, service_fn},
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    message::BackendMessage,
+    model::{PushEnvelope, SubscriptionName, TopicName},
+    ui_sender::UiSender,
+};
+
+/// The path that Pub/Sub pushes messages to.
+const PUSH_PATH: &str = "/push";
+
+/// The Topics of the push Subscriptions whose messages are being received.
+pub(crate) type PushRoutes = Arc<Mutex<HashMap<SubscriptionName, TopicName>>>;
+
+/// A local HTTP endpoint that push Subscriptions deliver messages to, which are passed on to
+/// the frontend as if they had been pulled.
+pub(crate) struct PushReceiver {
+    pub port: u16,
+    cancel_token: CancellationToken,
+}
+
+impl PushReceiver {
+    /// Must be called from within the runtime that will serve the requests.
+    pub fn start(port: u16, routes: PushRoutes, back_tx: UiSender) -> Result<Self, hyper::Error> {
+        let builder = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], port)))?;
+        let cancel_token = CancellationToken::new();
+
+        let make_service = make_service_fn(move |_| {
+            let routes = routes.clone();
+            let back_tx = back_tx.clone();
+
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    handle_push(request, routes.clone(), back_tx.clone())
+                }))
+            }
+        });
+
+        let shutdown_token = cancel_token.clone();
+        let server = builder
+            .serve(make_service)
+            .with_graceful_shutdown(async move { shutdown_token.cancelled().await });
+
+        tokio::spawn(async move {
+            if let Err(err) = server.await {
+                eprintln!("{}", err);
+            }
+        });
+
+        Ok(Self { port, cancel_token })
+    }
+}
+
+impl Drop for PushReceiver {
+    fn drop(&mut self) {
+        self.cancel_token.cancel();
+    }
+}
+
+pub(crate) fn push_endpoint(port: u16) -> String {
+    format!("http://localhost:{}{}", port, PUSH_PATH)
+}
+
+/// Responds with a success status to acknowledge the message. Messages for Subscriptions that
+/// are not being received are refused, so that Pub/Sub redelivers them later.
+async fn handle_push(
+    request: Request<Body>,
+    routes: PushRoutes,
+    back_tx: UiSender,
+) -> Result<Response<Body>, Infallible> {
+    if request.method() != Method::POST || request.uri().path() != PUSH_PATH {
+        return Ok(status_response(StatusCode::NOT_FOUND));
+    }
+
+    let body = match hyper::body::to_bytes(request.into_body()).await {
+        Ok(body) => body,
+        Err(err) => {
+            eprintln!("{}", err);
+            return Ok(status_response(StatusCode::BAD_REQUEST));
+        }
+    };
+
+    let (sub_name, message) = match serde_json::from_slice::<PushEnvelope>(&body)
+        .map_err(|err| err.to_string())
+        .and_then(PushEnvelope::into_message)
+    {
+        Ok(pushed) => pushed,
+        Err(err) => {
+            eprintln!("{}", err);
+            return Ok(status_response(StatusCode::BAD_REQUEST));
+        }
+    };
+
+    let topic_name = routes.lock().unwrap().get(&sub_name).cloned();
+
+    let status = match topic_name {
+        Some(topic_name) => match back_tx
+            .send(BackendMessage::MessagesReceived(topic_name, vec![message]))
+            .await
+        {
+            Ok(_) => StatusCode::NO_CONTENT,
+            Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+        },
+        None => StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    Ok(status_response(status))
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
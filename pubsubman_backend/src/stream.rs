@@ -0,0 +1,178 @@
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use google_cloud_pubsub::client::Client;
+use tokio::sync::mpsc::error::{SendError, TrySendError};
+
+use crate::{
+    message::{BackendError, BackendMessage},
+    model::{StreamState, SubscriptionName, TopicName},
+    subscription_exists,
+    ui_sender::UiSender,
+};
+
+/// The delay before resuming a stream that ended, doubling after each consecutive time.
+const INITIAL_RESUME_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESUME_DELAY: Duration = Duration::from_secs(60);
+
+/// The number of consecutive times a stream may end before it is no longer resumed.
+const MAX_RESUME_ATTEMPTS: u32 = 8;
+
+/// How long a stream must have lasted for its ending not to count as consecutive.
+const HEALTHY_STREAM_DURATION: Duration = Duration::from_secs(60);
+
+/// The most messages sent to the UI at once, from those that have already arrived.
+const MAX_BATCH_SIZE: usize = 500;
+
+/// The stream is reported as stalled once this few messages can be sent before the UI catches
+/// up, so that there is still room to report it rather than waiting on the queue it is about.
+const STALLED_CAPACITY: usize = 2;
+
+/// Sending to the UI only fails once it has closed, after which there is no point streaming.
+type SendResult = Result<(), SendError<BackendMessage>>;
+
+/// Streams messages from the Subscription until cancelled, reporting the state of the stream
+/// and resuming it with a backoff whenever it ends.
+pub(crate) async fn stream_messages(
+    client: &Client,
+    back_tx: &UiSender,
+    topic_name: TopicName,
+    sub_name: SubscriptionName,
+) -> SendResult {
+    let mut attempt = 0;
+
+    loop {
+        send_state(back_tx, &topic_name, &sub_name, StreamState::Connecting).await?;
+
+        let started = Instant::now();
+
+        match subscription_exists(client, &topic_name, &sub_name).await {
+            Ok(true) => receive_messages(client, back_tx, &topic_name, &sub_name).await?,
+            Ok(false) => return lost(back_tx, topic_name, sub_name).await,
+            Err(status) => eprintln!("{}", status),
+        }
+
+        // The stream ends without an error when the Subscription is deleted, so check whether
+        // that is why it ended.
+        if let Ok(false) = subscription_exists(client, &topic_name, &sub_name).await {
+            return lost(back_tx, topic_name, sub_name).await;
+        }
+
+        if started.elapsed() >= HEALTHY_STREAM_DURATION {
+            attempt = 0;
+        }
+
+        attempt += 1;
+
+        if attempt > MAX_RESUME_ATTEMPTS {
+            return back_tx
+                .send(BackendMessage::Error(BackendError::StreamMessagesFailed(
+                    topic_name, sub_name,
+                )))
+                .await;
+        }
+
+        let delay = INITIAL_RESUME_DELAY
+            .saturating_mul(2u32.pow(attempt - 1))
+            .min(MAX_RESUME_DELAY);
+
+        send_state(
+            back_tx,
+            &topic_name,
+            &sub_name,
+            StreamState::Resuming { attempt, delay },
+        )
+        .await?;
+
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Receives messages until the stream ends, sending those that have arrived together.
+async fn receive_messages(
+    client: &Client,
+    back_tx: &UiSender,
+    topic_name: &TopicName,
+    sub_name: &SubscriptionName,
+) -> SendResult {
+    let stream = match client.subscription(&sub_name.0).subscribe(None).await {
+        Ok(stream) => stream,
+        Err(status) => {
+            eprintln!("{}", status);
+            return Ok(());
+        }
+    };
+
+    send_state(back_tx, topic_name, sub_name, StreamState::Streaming).await?;
+
+    let mut batches = stream.ready_chunks(MAX_BATCH_SIZE);
+    let mut is_stalled = false;
+
+    while let Some(messages) = batches.next().await {
+        futures::future::join_all(messages.iter().map(|message| message.ack())).await;
+
+        let message = BackendMessage::MessagesReceived(
+            topic_name.clone(),
+            messages.into_iter().map(Into::into).collect(),
+        );
+
+        let stalled = back_tx.capacity() <= STALLED_CAPACITY;
+
+        if stalled != is_stalled {
+            let state = if stalled {
+                StreamState::Stalled
+            } else {
+                StreamState::Streaming
+            };
+
+            // A report that does not fit is retried with the next batch.
+            if try_send_state(back_tx, topic_name, sub_name, state)? {
+                is_stalled = stalled;
+            }
+        }
+
+        back_tx.send(message).await?;
+    }
+
+    Ok(())
+}
+
+/// Reports the state of the stream without waiting for room in the queue, returning whether
+/// there was room.
+fn try_send_state(
+    back_tx: &UiSender,
+    topic_name: &TopicName,
+    sub_name: &SubscriptionName,
+    state: StreamState,
+) -> Result<bool, SendError<BackendMessage>> {
+    match back_tx.try_send(BackendMessage::StreamStateChanged(
+        topic_name.clone(),
+        sub_name.clone(),
+        state,
+    )) {
+        Ok(()) => Ok(true),
+        Err(TrySendError::Full(_)) => Ok(false),
+        Err(TrySendError::Closed(message)) => Err(SendError(message)),
+    }
+}
+
+async fn send_state(
+    back_tx: &UiSender,
+    topic_name: &TopicName,
+    sub_name: &SubscriptionName,
+    state: StreamState,
+) -> SendResult {
+    back_tx
+        .send(BackendMessage::StreamStateChanged(
+            topic_name.clone(),
+            sub_name.clone(),
+            state,
+        ))
+        .await
+}
+
+async fn lost(back_tx: &UiSender, topic_name: TopicName, sub_name: SubscriptionName) -> SendResult {
+    back_tx
+        .send(BackendMessage::SubscriptionLost(topic_name, sub_name))
+        .await
+}
@@ -0,0 +1,41 @@
+use tokio::sync::mpsc::{
+    Sender,
+    error::{SendError, TrySendError},
+};
+
+use crate::message::BackendMessage;
+
+/// Sends messages to the UI, waking it to handle them, so that it need not repaint
+/// continuously in order to notice them.
+#[derive(Clone)]
+pub(crate) struct UiSender {
+    back_tx: Sender<BackendMessage>,
+    egui_ctx: egui::Context,
+}
+
+impl UiSender {
+    pub fn new(back_tx: Sender<BackendMessage>, egui_ctx: egui::Context) -> Self {
+        Self { back_tx, egui_ctx }
+    }
+
+    pub async fn send(&self, message: BackendMessage) -> Result<(), SendError<BackendMessage>> {
+        let result = self.back_tx.send(message).await;
+        self.egui_ctx.request_repaint();
+        result
+    }
+
+    /// The number of messages that can be sent before the UI has to handle those already sent.
+    pub fn capacity(&self) -> usize {
+        self.back_tx.capacity()
+    }
+
+    pub fn try_send(&self, message: BackendMessage) -> Result<(), TrySendError<BackendMessage>> {
+        let result = self.back_tx.try_send(message);
+
+        if result.is_ok() {
+            self.egui_ctx.request_repaint();
+        }
+
+        result
+    }
+}